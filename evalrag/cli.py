@@ -0,0 +1,587 @@
+# evalrag/cli.py
+# Command-line entrypoints:
+#   gate: run an evaluation suite and fail (exit code 1) when it breaks the policy thresholds.
+# Exit codes: 0 ok, 1 gate failed, 2 usage error, 3 nothing produced (no pairs generated,
+#             no annotations exported), 4 dataset content does not match its hash.
+#   calibrate: score a human-labeled calibration set with several judges and report their agreement.
+#   export-annotations: write the human-annotated items of a run as a calibration JSONL set.
+#   ingest: add files to the persisted FAISS index, or sync directories with it (added/changed/deleted files).
+#   generate: build a synthetic QA testset from source documents.
+#   dedup: drop duplicate questions from a dataset and report chunk leakage.
+#   perturb: build an adversarial/robustness variant of a dataset.
+#   dataset: list, inspect, register, verify and import/export (HuggingFace) registry datasets.
+#   benchmark: compare the retrieval metrics of chunkers and chunk sizes on a dataset.
+# Usage: python -m evalrag.cli gate --suite smoke --junit reports/junit.xml --markdown reports/summary.md
+#        python -m evalrag.cli export-annotations smoke-20250101-120000 data/calibration.jsonl
+#        python -m evalrag.cli calibrate data/calibration.jsonl --repeats 2
+#        python -m evalrag.cli generate data/docs/handbook.pdf --name testset --n 50
+#        python -m evalrag.cli perturb testset@1.0.0 --name testset-adversarial
+#        python -m evalrag.cli dataset export-hf testset@1.2.0 exports/testset
+#        python -m evalrag.cli benchmark data/docs --dataset testset@1.0.0 --sizes 400,800
+
+import argparse
+import sys
+import time
+from pathlib import Path
+
+from .core.config import BASE_DIR, POLICY_FILE, load_core_config
+from .core.eval.gate import GateReport, check_policy, load_policy, to_junit_xml, to_markdown
+
+EXIT_OK = 0
+EXIT_GATE_FAILED = 1
+EXIT_USAGE = 2
+EXIT_EMPTY = 3
+EXIT_INTEGRITY = 4
+
+
+def _write(path: str | None, text: str) -> None:
+    if path:
+        Path(path).parent.mkdir(parents=True, exist_ok=True)
+        Path(path).write_text(text, encoding="utf-8")
+
+
+def cmd_gate(args) -> int:
+    """
+    Run (or reuse) an evaluation run of a suite and check it against the policy.
+    """
+    from .core.eval import Evaluator, EvaluationRunner, RunStore
+    from .core.executor import guard_client
+    from .core.llm import get_llm_client
+    from .core.rag import build_rag
+
+    if args.skip_run and not args.run_id:
+        print("error: --skip-run needs --run-id", file=sys.stderr)
+        return EXIT_USAGE
+    try:
+        policy = load_policy(args.policy, args.suite, require_dataset=not args.skip_run)
+    except (OSError, KeyError, ValueError) as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+
+    settings = load_core_config()
+    store = RunStore(settings.store.url)
+
+    if args.skip_run:
+        run = store.get_run(args.run_id)
+        if run is None or not run.get("summary"):
+            print(f"error: run '{args.run_id}' has no stored summary", file=sys.stderr)
+            return EXIT_USAGE
+        run_id, summary = args.run_id, run["summary"]
+    else:
+        # a policy dataset is a file path (relative to the repo) or a registry `name@version`
+        dataset = Path(policy["dataset"])
+        dataset = dataset if dataset.is_absolute() else BASE_DIR / dataset
+        dataset = str(dataset) if dataset.is_file() else policy["dataset"]
+        judge_client = guard_client(
+            get_llm_client(provider=settings.eval.judge_provider, model_name=settings.eval.judge_model),
+            provider=settings.eval.judge_provider,
+            execution=settings.execution,
+        )
+        runner = EvaluationRunner(
+            rag=build_rag(settings),
+            evaluator=Evaluator(settings.eval, judge_client=judge_client, metrics=policy.get("metrics")),
+            runs_dir=settings.eval.runs_dir,
+            concurrency=settings.execution.concurrency,
+            store=store,
+        )
+        run_id = args.run_id or f"{args.suite}-{time.strftime('%Y%m%d-%H%M%S')}"
+        summary = runner.run(
+            dataset,
+            run_id=run_id,
+            limit=args.limit or policy.get("limit"),
+            split=policy.get("split"),
+        )
+
+    baseline = policy.get("baseline") or {}
+    baseline_run_id = args.baseline or baseline.get("run_id")
+    comparison = None
+    if baseline_run_id:
+        if store.get_run(baseline_run_id) is None:
+            print(f"error: baseline run '{baseline_run_id}' not found in the run store", file=sys.stderr)
+            return EXIT_USAGE
+        comparison = store.compare(
+            baseline_run_id,
+            run_id,
+            metrics=baseline.get("metrics"),
+            tolerance=baseline.get("tolerance", 0.0),
+        )
+
+    report = GateReport(
+        suite=args.suite,
+        run_id=run_id,
+        checks=check_policy(policy, summary, comparison),
+        summary=summary,
+        baseline_run_id=baseline_run_id,
+    )
+    markdown = to_markdown(report, comparison)
+    _write(args.junit, to_junit_xml(report))
+    _write(args.markdown, markdown)
+    print(markdown)
+    return EXIT_OK if report.passed else EXIT_GATE_FAILED
+
+
+def cmd_calibrate(args) -> int:
+    """
+    Score a human-labeled calibration set with the calibration judges and print their agreement.
+    """
+    import json
+
+    from .core.config import load_prompt_config
+    from .core.eval import agreement_report, build_judges, load_calibration_set, score_with_judges
+
+    settings = load_core_config()
+    specs = settings.eval.calibration_judges or [
+        {"provider": settings.eval.judge_provider, "model": settings.eval.judge_model}
+    ]
+    try:
+        samples, human = load_calibration_set(args.path)
+    except (OSError, KeyError, ValueError) as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+
+    judges = build_judges(
+        specs, load_prompt_config(), settings.execution, output_format=settings.eval.judge_output_format
+    )
+    ratings = score_with_judges(samples, judges, repeats=args.repeats)
+    labeled = any(v is not None for values in human.values() for v in values)
+    try:
+        report = agreement_report(ratings, human if labeled else None)
+    except ValueError as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+
+    _write(args.json, json.dumps({"ratings": ratings, "report": report}, indent=2))
+    print(f"{len(samples)} samples scored by {', '.join(sorted(ratings))}")
+    for criterion, entry in report.items():
+        print(criterion)
+        if "inter_judge" in entry:
+            print("  inter-judge: " + ", ".join(f"{k} {v:.3f}" for k, v in entry["inter_judge"].items()))
+        for judge, stats in entry.get("vs_human", {}).items():
+            print(f"  {judge} vs human: " + ", ".join(
+                f"{k} {v}" if k == "n" else f"{k} {v:.3f}" for k, v in stats.items()
+            ))
+    return EXIT_OK
+
+
+def cmd_export_annotations(args) -> int:
+    """
+    Export the annotated items of a stored run, with their human scores, for `calibrate`.
+    """
+    from .core.eval import RunStore
+
+    store = RunStore(load_core_config().store.url)
+    if store.get_run(args.run_id) is None:
+        print(f"error: run '{args.run_id}' not found in the run store", file=sys.stderr)
+        return EXIT_USAGE
+    n = store.export_annotations(args.run_id, args.path)
+    print(f"{n} annotated items of {args.run_id} written to {args.path}")
+    return EXIT_OK if n else EXIT_EMPTY
+
+
+def cmd_ingest(args) -> int:
+    """
+    Index source files, or sync source directories, into the persisted vector store.
+    """
+    from .core.ingestion import Ingestion
+
+    settings = load_core_config()
+    ingestion = Ingestion(settings.ingestion)
+    embeddings = None if args.dry_run else ingestion.get_embedding_model(provider=settings.ingestion.provider)
+    for source in args.sources:
+        if Path(source).is_dir():
+            plan = ingestion.sync_dir(source, dry_run=args.dry_run, embeddings=embeddings)
+            prefix = "would " if args.dry_run else ""
+            for action, files in (("add", plan.added), ("update", plan.updated), ("delete", plan.deleted)):
+                for filename in files:
+                    print(f"{prefix}{action}: {filename}")
+            print(f"{source}: {len(plan.added)} added, {len(plan.updated)} updated, "
+                  f"{len(plan.deleted)} deleted, {len(plan.unchanged)} unchanged")
+        elif args.dry_run:
+            print(f"would index: {source}")
+        else:
+            n_chunks = ingestion.indexing(filename=source, embeddings=embeddings, force=args.force)
+            print(f"{source}: {f'{n_chunks} chunks indexed' if n_chunks else 'unchanged, skipped'}")
+    print(f"{len(ingestion.load_index_state(settings.ingestion.vector_store_path))} documents indexed "
+          f"in {settings.ingestion.vector_store_path}")
+    return EXIT_OK
+
+
+def cmd_generate(args) -> int:
+    """
+    Generate a synthetic QA dataset from source files and register a new version of it.
+    """
+    from dataclasses import asdict
+
+    from .core.ingestion import Ingestion
+    from .datasets import QuestionCritic, assign_splits, build_generator, corpus_snapshot, write_dataset
+
+    settings = load_core_config()
+    ingestion = Ingestion(settings.ingestion)
+    docs = []
+    for source in args.sources:
+        docs += ingestion.loader(filename=source)
+    chunks = ingestion.splitter(
+        docs=docs,
+        chunk_size=settings.ingestion.default_chunk_size,
+        chunk_overlap=settings.ingestion.default_chunk_overlap,
+    )
+
+    generator = build_generator(settings)
+    pairs = generator.generate(chunks, n_samples=args.n or settings.generation.n_samples)
+    critique = None
+    if not args.no_critique:
+        critic = QuestionCritic(
+            generator.llm_client,
+            thresholds=settings.generation.critique_thresholds,
+            concurrency=settings.execution.concurrency,
+        )
+        pairs, critique = critic.filter(pairs)
+        print(f"critique kept {critique.n_kept}/{critique.n_in} pairs; "
+              f"dropped by {critique.dropped_by}, unrated {critique.unrated}")
+    dedup = None
+    if not args.no_dedup:
+        pairs, dedup = _deduplicate(pairs, settings, semantic=not args.lexical_only)
+    # questions of one chunk share a split so no chunk leaks across splits
+    splits = assign_splits(
+        pairs, settings.generation.splits, seed=settings.generation.seed or 0, group_key="chunk_id"
+    ) if settings.generation.splits and pairs else None
+    version = write_dataset(
+        pairs,
+        output_dir=args.output_dir or settings.generation.output_dir,
+        name=args.name,
+        splits=splits,
+        metadata={
+            "sources": corpus_snapshot(args.sources),
+            "generation": asdict(settings.generation),
+            "critique_thresholds": critique.thresholds if critique else None,
+            "chunking": {
+                "chunk_size": settings.ingestion.default_chunk_size,
+                "chunk_overlap": settings.ingestion.default_chunk_overlap,
+            },
+            "stats": generator.stats,
+            "critique": asdict(critique) if critique else None,
+            "dedup": asdict(dedup) if dedup else None,
+        },
+    )
+    print(f"{len(pairs)} QA pairs registered as {version.ref} ({generator.stats})")
+    return EXIT_OK if pairs else EXIT_EMPTY
+
+
+def _deduplicate(items, settings, semantic: bool = True):
+    from .core.ingestion import Ingestion
+    from .datasets import deduplicate
+
+    kept, report = deduplicate(
+        items,
+        embedder=Ingestion.get_embedding_model(provider=settings.rag.provider) if semantic else None,
+        lexical_threshold=settings.generation.dedup_lexical_threshold,
+        semantic_threshold=settings.generation.dedup_semantic_threshold,
+        max_questions_per_chunk=settings.generation.max_questions_per_chunk,
+    )
+    print(f"dedup kept {report.n_kept}/{report.n_in} items in {len(report.clusters)} duplicate clusters "
+          f"(links {report.links}); {len(report.leakage)} chunks flagged for leakage")
+    for chunk_id, entry in report.leakage.items():
+        print(f"  leakage: {chunk_id} backs {entry['n_questions']} questions, splits {entry['splits'] or '-'}")
+    return kept, report
+
+
+def _read_dataset(dataset: str, registry):
+    """
+    Records of a dataset file or registry reference, and where they came from.
+    """
+    from .core.eval.runner import read_records
+
+    if Path(dataset).is_file():
+        return read_records(dataset), {"path": str(dataset)}
+    version = registry.resolve(dataset)
+    return registry.load(version.ref), {"ref": version.ref, "content_hash": version.content_hash}
+
+
+def _splits_of(records):
+    splits = {}
+    for record in records:
+        if record.get("split"):
+            splits.setdefault(record["split"], []).append(record["id"])
+    return splits or None
+
+
+def cmd_dedup(args) -> int:
+    """
+    Deduplicate a dataset file or registry dataset and register the result as a new dataset version.
+    """
+    from dataclasses import asdict
+
+    from .datasets import DatasetRegistry, write_dataset
+
+    settings = load_core_config()
+    registry = DatasetRegistry(args.output_dir or settings.generation.output_dir)
+    records, source = _read_dataset(args.dataset, registry)
+    kept, report = _deduplicate(records, settings, semantic=not args.lexical_only)
+    version = write_dataset(
+        kept,
+        output_dir=registry.root,
+        name=args.name,
+        splits=_splits_of(kept),
+        metadata={"derived_from": source, "dedup": asdict(report)},
+    )
+    print(f"{len(kept)} items registered as {version.ref}")
+    return EXIT_OK
+
+
+def cmd_perturb(args) -> int:
+    """
+    Perturb a dataset file or registry dataset and register the robustness variant as a new dataset version.
+    """
+    from dataclasses import asdict
+
+    from .datasets import DatasetRegistry, Perturber, write_dataset
+
+    settings = load_core_config()
+    registry = DatasetRegistry(args.output_dir or settings.generation.output_dir)
+    records, source = _read_dataset(args.dataset, registry)
+    try:
+        perturber = Perturber(
+            families=args.families.split(",") if args.families else settings.generation.perturbation_families,
+            seed=settings.generation.seed or 0,
+            typo_rate=settings.generation.perturbation_typo_rate,
+        )
+    except ValueError as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+    perturbed, stats = perturber.build(records)
+    version = write_dataset(
+        perturbed,
+        output_dir=registry.root,
+        name=args.name,
+        splits=_splits_of(perturbed),
+        metadata={
+            "derived_from": source,
+            "perturbation": {"families": perturber.families, "seed": perturber.seed, "typo_rate": perturber.typo_rate},
+            "stats": asdict(stats),
+        },
+    )
+    print(f"{len(perturbed)} items ({stats.n_clean} clean, variants {stats.generated}, "
+          f"not applicable {stats.skipped}) registered as {version.ref}")
+    return EXIT_OK
+
+
+def cmd_dataset(args) -> int:
+    """
+    Manage the dataset registry.
+    """
+    import json
+
+    from .core.eval.runner import read_records
+    from .datasets import DatasetRegistry, assign_splits
+
+    settings = load_core_config()
+    registry = DatasetRegistry(args.registry or settings.generation.output_dir)
+    try:
+        if args.action == "list":
+            for version in registry.list():
+                versions = registry.versions(version.name)
+                print(f"{version.ref}\t{version.manifest['n_items']} items\t"
+                      f"{version.content_hash[:12]}\t({len(versions)} versions: {', '.join(versions)})")
+        elif args.action == "show":
+            print(json.dumps(registry.resolve(args.ref).manifest, indent=2))
+        elif args.action == "verify":
+            version = registry.resolve(args.ref)
+            if not registry.verify(version.ref):
+                print(f"{version.ref}: content does not match hash {version.content_hash}", file=sys.stderr)
+                return EXIT_INTEGRITY
+            print(f"{version.ref}: ok ({version.content_hash})")
+        elif args.action == "register":
+            records = read_records(args.path)
+            splits = None
+            if args.splits:
+                ratios = {k: float(v) for k, v in (part.split("=") for part in args.splits.split(","))}
+                splits = assign_splits(records, ratios, seed=settings.generation.seed or 0, group_key=args.group_by)
+            version = registry.register(
+                args.name,
+                records,
+                version=args.version,
+                bump=args.bump,
+                provenance={"imported_from": str(args.path)},
+                splits=splits,
+                description=args.description or "",
+            )
+            print(f"registered {version.ref} ({version.manifest['n_items']} items)")
+        elif args.action == "export-hf":
+            print(f"exported to {registry.export_hf(args.ref, args.path)}")
+        elif args.action == "import-hf":
+            column_map = dict(part.split("=") for part in args.columns.split(",")) if args.columns else None
+            version = registry.import_hf(args.path, args.name, version=args.version, column_map=column_map)
+            print(f"registered {version.ref} ({version.manifest['n_items']} items)")
+    except (KeyError, ValueError, OSError) as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+    return EXIT_OK
+
+
+def cmd_benchmark(args) -> int:
+    """
+    Index the corpus once per chunking configuration and compare their retrieval metrics on a dataset.
+    """
+    import json
+    from dataclasses import asdict
+
+    from .core.eval.benchmark import ChunkingBenchmark, chunking_grid, to_markdown_table
+    from .datasets import DatasetRegistry
+
+    settings = load_core_config()
+    grid_config = settings.ingestion.benchmark or {}
+
+    def _list(value, default, cast=str):
+        # a comma-separated flag overrides the configured list
+        return [cast(v) for v in (value.split(",") if value else default)]
+
+    try:
+        grid = chunking_grid(
+            chunkers=_list(args.chunkers, grid_config.get("chunkers", [settings.ingestion.chunker])),
+            chunk_sizes=_list(args.sizes, grid_config.get("chunk_sizes", [settings.ingestion.default_chunk_size]), int),
+            chunk_overlaps=_list(
+                args.overlaps, grid_config.get("chunk_overlaps", [settings.ingestion.default_chunk_overlap]), int),
+        )
+    except (KeyError, ValueError) as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+    if not grid:
+        print("error: empty grid (every overlap is at least the chunk size)", file=sys.stderr)
+        return EXIT_USAGE
+
+    k_values = _list(args.k, (settings.eval.metric_params.get("ir") or {}).get("k_values", [1, 3, 5, 10]), int)
+    benchmark = ChunkingBenchmark(
+        settings.ingestion,
+        k_values=k_values,
+        min_overlap=grid_config.get("min_overlap", 0.5),
+        work_dir=args.work_dir,
+        keep_indexes=args.keep_indexes,
+    )
+    try:
+        results = benchmark.run(
+            args.sources,
+            args.dataset,
+            grid,
+            split=args.split,
+            limit=args.limit,
+            registry=DatasetRegistry(settings.generation.output_dir),
+        )
+    except (KeyError, ValueError, OSError) as e:
+        print(f"error: {e}", file=sys.stderr)
+        return EXIT_USAGE
+
+    table = to_markdown_table(results, k_values)
+    _write(args.markdown, table + "\n")
+    _write(args.json, json.dumps([asdict(r) for r in results], indent=2))
+    print(f"{len(grid)} configurations, {results[0].n_items if results else 0} items of {args.dataset}\n")
+    print(table)
+    return EXIT_OK
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser(prog="evalrag", description="EvalRAG command-line tools")
+    commands = parser.add_subparsers(dest="command", required=True)
+
+    gate = commands.add_parser("gate", help="run an evaluation suite and enforce its quality policy")
+    gate.add_argument("--suite", required=True, help="suite name defined in the policy file")
+    gate.add_argument("--policy", default=str(POLICY_FILE), help="policy YAML file")
+    gate.add_argument("--run-id", help="run id to create/resume, or to gate with --skip-run")
+    gate.add_argument("--skip-run", action="store_true", help="gate an existing run from the run store")
+    gate.add_argument("--baseline", help="baseline run id, overriding the policy")
+    gate.add_argument("--limit", type=int, help="cap on dataset items, overriding the policy")
+    gate.add_argument("--junit", help="write a JUnit XML report to this path")
+    gate.add_argument("--markdown", help="write a Markdown summary to this path")
+    gate.set_defaults(func=cmd_gate)
+
+    calibrate = commands.add_parser("calibrate", help="measure judge agreement on a human-labeled set")
+    calibrate.add_argument("path", help="calibration JSONL with question, answer, contexts and human scores")
+    calibrate.add_argument("--repeats", type=int, default=1,
+                           help="scorings per judge, reported as separate raters (self-consistency)")
+    calibrate.add_argument("--json", help="write the ratings and agreement report as JSON to this path")
+    calibrate.set_defaults(func=cmd_calibrate)
+
+    export_annotations = commands.add_parser("export-annotations", help="export the human annotations of a run")
+    export_annotations.add_argument("run_id", help="run id in the run store")
+    export_annotations.add_argument("path", help="output JSONL, the calibration set read by calibrate")
+    export_annotations.set_defaults(func=cmd_export_annotations)
+
+    ingest = commands.add_parser("ingest", help="add files to the vector store")
+    ingest.add_argument("sources", nargs="+", help="files to index, or directories to sync recursively")
+    ingest.add_argument("--force", action="store_true", help="re-index files even when unchanged")
+    ingest.add_argument("--dry-run", action="store_true", help="print the planned adds/updates/deletes only")
+    ingest.set_defaults(func=cmd_ingest)
+
+    generate = commands.add_parser("generate", help="generate a synthetic QA testset from documents")
+    generate.add_argument("sources", nargs="+", help="source files to generate questions from")
+    generate.add_argument("--name", required=True, help="dataset name")
+    generate.add_argument("--n", type=int, help="number of chunks to sample, overriding the config")
+    generate.add_argument("--output-dir", help="dataset directory, overriding the config")
+    generate.add_argument("--no-critique", action="store_true", help="keep pairs without critique filtering")
+    generate.add_argument("--no-dedup", action="store_true", help="keep duplicate questions")
+    generate.add_argument("--lexical-only", action="store_true", help="skip embedding-based duplicate detection")
+    generate.set_defaults(func=cmd_generate)
+
+    dedup = commands.add_parser("dedup", help="deduplicate a dataset into a new dataset version")
+    dedup.add_argument("dataset", help="dataset file or registry name@version")
+    dedup.add_argument("--name", required=True, help="dataset name of the curated version")
+    dedup.add_argument("--output-dir", help="dataset directory, overriding the config")
+    dedup.add_argument("--lexical-only", action="store_true", help="skip embedding-based duplicate detection")
+    dedup.set_defaults(func=cmd_dedup)
+
+    perturb = commands.add_parser("perturb", help="build a robustness dataset of perturbed questions")
+    perturb.add_argument("dataset", help="dataset file or registry name@version")
+    perturb.add_argument("--name", required=True, help="dataset name of the robustness variant")
+    perturb.add_argument("--families", help="comma-separated perturbation families, overriding the config")
+    perturb.add_argument("--output-dir", help="dataset directory, overriding the config")
+    perturb.set_defaults(func=cmd_perturb)
+
+    dataset = commands.add_parser("dataset", help="manage the versioned dataset registry")
+    dataset.add_argument("--registry", help="registry directory, overriding the config")
+    actions = dataset.add_subparsers(dest="action", required=True)
+    actions.add_parser("list", help="list datasets and their versions")
+    for action, help_text in (("show", "print the manifest of a version"),
+                              ("verify", "check a version against its content hash")):
+        actions.add_parser(action, help=help_text).add_argument("ref", help="name@version, or name for the latest")
+    register = actions.add_parser("register", help="register a dataset file as a new version")
+    register.add_argument("path", help="JSONL, JSON, CSV or Parquet dataset file")
+    register.add_argument("--name", required=True, help="dataset name")
+    register.add_argument("--version", help="explicit MAJOR.MINOR.PATCH version")
+    register.add_argument("--bump", choices=["major", "minor", "patch"], default="minor",
+                          help="version part to bump when --version is not given")
+    register.add_argument("--splits", help="split ratios, e.g. dev=0.2,test=0.8")
+    register.add_argument("--group-by", help="field whose items must share a split, e.g. chunk_id")
+    register.add_argument("--description", help="notes stored in the manifest")
+    export_hf = actions.add_parser("export-hf", help="save a version in HuggingFace datasets format")
+    export_hf.add_argument("ref", help="name@version, or name for the latest")
+    export_hf.add_argument("path", help="output directory")
+    import_hf = actions.add_parser("import-hf", help="register a HuggingFace dataset saved with save_to_disk")
+    import_hf.add_argument("path", help="dataset directory")
+    import_hf.add_argument("--name", required=True, help="dataset name")
+    import_hf.add_argument("--version", help="explicit MAJOR.MINOR.PATCH version")
+    import_hf.add_argument("--columns", help="column renames, e.g. query=question,ground_truth=answer")
+    dataset.set_defaults(func=cmd_dataset)
+
+    benchmark = commands.add_parser("benchmark", help="compare chunking strategies by retrieval metrics")
+    benchmark.add_argument("sources", nargs="+", help="corpus files or directories the dataset was built from")
+    benchmark.add_argument("--dataset", required=True, help="dataset file or registry name@version")
+    benchmark.add_argument("--split", help="dataset split to evaluate")
+    benchmark.add_argument("--limit", type=int, help="cap on dataset items")
+    benchmark.add_argument("--chunkers", help="comma-separated chunker names, overriding the config")
+    benchmark.add_argument("--sizes", help="comma-separated chunk sizes, overriding the config")
+    benchmark.add_argument("--overlaps", help="comma-separated chunk overlaps, overriding the config")
+    benchmark.add_argument("--k", help="comma-separated hit@k cut-offs, e.g. 1,5,10")
+    benchmark.add_argument("--work-dir", help="directory of the temporary indexes")
+    benchmark.add_argument("--keep-indexes", action="store_true", help="keep the temporary indexes")
+    benchmark.add_argument("--json", help="write the results as JSON to this path")
+    benchmark.add_argument("--markdown", help="write the results table to this path")
+    benchmark.set_defaults(func=cmd_benchmark)
+    return parser
+
+
+def main(argv=None) -> int:
+    args = build_parser().parse_args(argv)
+    return args.func(args)
+
+
+if __name__ == "__main__":
+    sys.exit(main())
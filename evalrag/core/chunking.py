@@ -0,0 +1,253 @@
+# evalrag/core/chunking.py
+
+"""
+Chunking strategies selectable from `ingestion.chunker` in `core.yaml`.
+
+- recursive: `RecursiveCharacterTextSplitter`, sizes in characters.
+- token: the same splitter measuring sizes in tiktoken tokens.
+- markdown_header / html_header: split on headings first, then on size
+  within each section.
+- sentence_window: one chunk per sentence, embedded alone, with the
+  surrounding sentences in `window` metadata; retrieval returns the
+  window.
+- semantic: split where the embedding similarity of consecutive sentences
+  drops, then on size.
+
+Every chunk records the headings leading to it in `heading_path` ("" when
+the source has no headings), so retrieval results carry their section.
+"""
+
+import re
+from typing import Any, Dict, List
+
+from langchain_core.documents import Document
+from langchain_text_splitters import RecursiveCharacterTextSplitter
+
+from .loaders import HEADING_SEPARATOR, html_sections, markdown_sections
+
+CHUNKER_REGISTRY: Dict[str, type] = {}
+
+class Chunker:
+    """
+    Base class for chunking strategies.
+
+    Subclasses set `name` and implement `split_document`; chunkers that
+    embed text set `needs_embeddings` so `Ingestion` provides a model, and
+    chunkers ignoring `chunk_size`/`chunk_overlap` unset `sized`.
+
+    Args:
+        chunk_size: Maximum chunk size (characters, or tokens for `token`).
+        chunk_overlap: Overlap between consecutive chunks of a section.
+        embeddings: Embeddings model for chunkers that need one.
+        **params: Chunker-specific options (`ingestion.chunker_params`).
+    """
+
+    name: str = ""
+    needs_embeddings: bool = False
+    sized: bool = True
+
+    def __init__(self, chunk_size: int = 800, chunk_overlap: int = 100, embeddings=None, **params) -> None:
+        self.chunk_size = chunk_size
+        self.chunk_overlap = chunk_overlap
+        self.embeddings = embeddings
+        self.params = params
+
+    def size_splitter(self) -> RecursiveCharacterTextSplitter:
+        return RecursiveCharacterTextSplitter(
+            chunk_size=self.chunk_size,
+            chunk_overlap=self.chunk_overlap,
+            add_start_index=True,
+        )
+
+    def split_document(self, doc: Document) -> List[Document]:
+        raise NotImplementedError
+
+    def split(self, docs: List[Document]) -> List[Document]:
+        """
+        Chunk every document, keeping its metadata on the chunks.
+        """
+        chunks = []
+        for doc in docs:
+            for chunk in self.split_document(doc):
+                chunk.metadata.setdefault("heading_path", "")
+                chunk.metadata["chunker"] = self.name
+                chunks.append(chunk)
+        return chunks
+
+
+def register_chunker(cls):
+    """
+    Class decorator adding a `Chunker` subclass to `CHUNKER_REGISTRY`, so it
+    can be selected by name from `ingestion.chunker` in `core.yaml`.
+    """
+    if not cls.name:
+        raise ValueError(f"{cls.__name__} must define a chunker name")
+    CHUNKER_REGISTRY[cls.name] = cls
+    return cls
+
+
+def get_chunker(
+        name: str,
+        chunk_size: int,
+        chunk_overlap: int,
+        params: Dict[str, Any] | None = None,
+        embeddings=None
+        ) -> Chunker:
+    """
+    Instantiate the registered chunker `name`.
+
+    Raises:
+        KeyError: If `name` is not registered.
+        ValueError: If the chunker needs embeddings and none are given.
+    """
+    if name not in CHUNKER_REGISTRY:
+        raise KeyError(f"Unknown chunker '{name}'. Registered: {sorted(CHUNKER_REGISTRY)}")
+    cls = CHUNKER_REGISTRY[name]
+    if cls.needs_embeddings and embeddings is None:
+        raise ValueError(f"Chunker '{name}' needs an embeddings model")
+    return cls(chunk_size=chunk_size, chunk_overlap=chunk_overlap, embeddings=embeddings, **(params or {}))
+
+
+@register_chunker
+class RecursiveChunker(Chunker):
+    name = "recursive"
+
+    def split_document(self, doc: Document) -> List[Document]:
+        return self.size_splitter().split_documents([doc])
+
+
+@register_chunker
+class TokenChunker(Chunker):
+    """
+    Sizes in tokens of the tiktoken `encoding_name` (default `cl100k_base`).
+    """
+    name = "token"
+
+    def size_splitter(self) -> RecursiveCharacterTextSplitter:
+        return RecursiveCharacterTextSplitter.from_tiktoken_encoder(
+            encoding_name=self.params.get("encoding_name", "cl100k_base"),
+            chunk_size=self.chunk_size,
+            chunk_overlap=self.chunk_overlap,
+            add_start_index=True,
+        )
+
+    def split_document(self, doc: Document) -> List[Document]:
+        return self.size_splitter().split_documents([doc])
+
+
+def _join_paths(outer: str, inner: str) -> str:
+    """
+    Heading path of a section found inside a document that already has one.
+
+    Loader sections start with their own heading, which then appears as
+    the last element of `outer` and the first of `inner`.
+    """
+    if not outer:
+        return inner
+    if not inner:
+        return outer
+    outer_parts, inner_parts = outer.split(HEADING_SEPARATOR), inner.split(HEADING_SEPARATOR)
+    if outer_parts[-1] == inner_parts[0]:
+        outer_parts = outer_parts[:-1]
+    return HEADING_SEPARATOR.join(outer_parts + inner_parts)
+
+
+class _HeaderChunker(Chunker):
+    def sections(self, doc: Document) -> List[Document]:
+        raise NotImplementedError
+
+    def split_document(self, doc: Document) -> List[Document]:
+        outer = doc.metadata.get("heading_path", "")
+        chunks = []
+        for section in self.sections(doc):
+            section.metadata = {
+                **doc.metadata,
+                "heading_path": _join_paths(outer, section.metadata.get("heading_path", "")),
+                "content_type": section.metadata.get("content_type", "text"),
+            }
+            chunks += self.size_splitter().split_documents([section])
+        return chunks
+
+
+@register_chunker
+class MarkdownHeaderChunker(_HeaderChunker):
+    name = "markdown_header"
+
+    def sections(self, doc: Document) -> List[Document]:
+        return markdown_sections(doc.page_content, doc.metadata.get("source", ""))
+
+
+@register_chunker
+class HTMLHeaderChunker(_HeaderChunker):
+    """
+    Splits raw HTML content on `h1`-`h6`. Documents that are not HTML
+    (e.g. already sectioned by the HTML loader) are only split on size.
+    """
+    name = "html_header"
+
+    def sections(self, doc: Document) -> List[Document]:
+        if not re.search(r"<(html|body|h[1-6]|p|div)\b", doc.page_content, re.IGNORECASE):
+            return [Document(page_content=doc.page_content, metadata={})]
+        return html_sections(doc.page_content, doc.metadata.get("source", ""))
+
+
+@register_chunker
+class SentenceWindowChunker(Chunker):
+    """
+    One chunk per sentence; `window` metadata holds the sentence with
+    `window_size` (default 2) sentences on each side.
+    """
+    name = "sentence_window"
+    sized = False
+
+    def split_document(self, doc: Document) -> List[Document]:
+        # imported here: `core.eval` imports `Ingestion`, which imports this module
+        from .eval.parsing import split_sentences
+
+        sentences = split_sentences(doc.page_content, line_breaks=False)
+        window_size = int(self.params.get("window_size", 2))
+        chunks = []
+        for i, sentence in enumerate(sentences):
+            window = " ".join(sentences[max(0, i - window_size):i + window_size + 1])
+            chunks.append(Document(page_content=sentence, metadata={**doc.metadata, "window": window}))
+        return chunks
+
+
+@register_chunker
+class SemanticChunker(Chunker):
+    """
+    Breaks between consecutive sentences whose embedding cosine distance is
+    above the `breakpoint_percentile` (default 95) of the document's
+    distances; resulting chunks above `chunk_size` are split on size.
+    """
+    name = "semantic"
+    needs_embeddings = True
+
+    def split_document(self, doc: Document) -> List[Document]:
+        # imported here: `core.eval` imports `Ingestion`, which imports this module
+        from .eval.parsing import split_sentences
+        from .eval.similarity import cosine_similarity, embed_texts
+
+        sentences = split_sentences(doc.page_content, line_breaks=False)
+        if len(sentences) < 3:
+            return self.size_splitter().split_documents([doc])
+
+        vectors = embed_texts(self.embeddings, sentences)
+        distances = [1 - cosine_similarity(a, b) for a, b in zip(vectors, vectors[1:])]
+        ranked = sorted(distances)
+        percentile = float(self.params.get("breakpoint_percentile", 95))
+        threshold = ranked[min(len(ranked) - 1, int(len(ranked) * percentile / 100))]
+
+        groups, current = [], [sentences[0]]
+        for sentence, distance in zip(sentences[1:], distances):
+            if distance >= threshold and distance > 0:
+                groups.append(current)
+                current = []
+            current.append(sentence)
+        groups.append(current)
+
+        chunks = []
+        for group in groups:
+            section = Document(page_content=" ".join(group), metadata=dict(doc.metadata))
+            chunks += self.size_splitter().split_documents([section])
+        return chunks
@@ -0,0 +1,94 @@
+# evalrag/core/eval/__init__.py
+
+from .agreement import (
+    agreement_report,
+    build_judges,
+    cohen_kappa,
+    fleiss_kappa,
+    krippendorff_alpha,
+    load_calibration_set,
+    score_with_judges,
+    spearman,
+)
+from .benchmark import BenchmarkResult, ChunkingBenchmark, ChunkingConfig, chunking_grid, to_markdown_table
+from .evaluator import Evaluator
+from .judge import CRITERIA, LLMJudge, JudgeResult, parse_judge_output
+from .metrics import (
+    Sample,
+    MetricResult,
+    Metric,
+    METRIC_REGISTRY,
+    register_metric,
+    get_metric,
+    build_metrics,
+)
+from .gate import GateCheck, GateReport, check_policy, load_policy
+from .faithfulness import ClaimFaithfulnessMetric
+from .relevancy import AnswerRelevancyMetric
+from .retrieval import (
+    ContextPrecisionMetric,
+    ContextRecallMetric,
+    IRMetric,
+    average_precision,
+    gold_relevance,
+    ir_metrics,
+)
+from .robustness import robustness_report
+from .runner import EvaluationRunner, EvalItem, load_dataset, resolve_dataset, aggregate_results, aggregate_by
+from .stats import significance_report
+from .store import RunStore, RunComparison
+from .scoring import resolve_weights, composite_score, is_hallucination, aggregate_scores
+
+__all__ = [
+    "Evaluator",
+    "CRITERIA",
+    "LLMJudge",
+    "JudgeResult",
+    "parse_judge_output",
+    "resolve_weights",
+    "composite_score",
+    "is_hallucination",
+    "aggregate_scores",
+    "Sample",
+    "MetricResult",
+    "Metric",
+    "METRIC_REGISTRY",
+    "register_metric",
+    "get_metric",
+    "build_metrics",
+    "ClaimFaithfulnessMetric",
+    "ContextPrecisionMetric",
+    "ContextRecallMetric",
+    "IRMetric",
+    "average_precision",
+    "gold_relevance",
+    "ir_metrics",
+    "AnswerRelevancyMetric",
+    "EvaluationRunner",
+    "EvalItem",
+    "load_dataset",
+    "resolve_dataset",
+    "aggregate_results",
+    "aggregate_by",
+    "robustness_report",
+    "ChunkingBenchmark",
+    "ChunkingConfig",
+    "BenchmarkResult",
+    "chunking_grid",
+    "to_markdown_table",
+    "RunStore",
+    "RunComparison",
+    "significance_report",
+    "agreement_report",
+    "build_judges",
+    "cohen_kappa",
+    "fleiss_kappa",
+    "krippendorff_alpha",
+    "load_calibration_set",
+    "score_with_judges",
+    "spearman",
+    "GateCheck",
+    "GateReport",
+    "check_policy",
+    "load_policy",
+]
@@ -0,0 +1,343 @@
+# evalrag/core/eval/agreement.py
+
+"""
+Inter-judge agreement and judge calibration.
+
+The same samples are scored by several judges (different providers or
+models, or repeated samples of one judge) and their ratings compared with
+each other and, when available, with human labels. Ratings are the 1-5
+judge scores; `None` marks a missing rating (e.g. a judge parse error).
+"""
+
+import itertools
+import json
+import math
+from collections import Counter
+from typing import Any, Dict, List, Sequence
+
+from ..executor import guard_client
+from ..llm import get_llm_client
+from .judge import CRITERIA, JudgeResult, LLMJudge
+from .metrics import Sample
+
+RATING_LEVELS = (1, 2, 3, 4, 5)
+
+
+def _paired(a: Sequence, b: Sequence) -> tuple:
+    pairs = [(x, y) for x, y in zip(a, b) if x is not None and y is not None]
+    return [x for x, _ in pairs], [y for _, y in pairs]
+
+
+def cohen_kappa(a: Sequence, b: Sequence, weights: str | None = "quadratic",
+                levels: Sequence = RATING_LEVELS) -> float:
+    """
+    Cohen's kappa between two raters.
+
+    Args:
+        a, b: Ratings of the same items by two raters.
+        weights: None for nominal kappa, "linear" or "quadratic" for
+            weighted kappa on ordinal scales.
+        levels: Ordered rating categories.
+
+    Returns:
+        Kappa in [-1, 1]; `nan` when fewer than two items are paired.
+
+    Raises:
+        ValueError: If a rating is not one of `levels` (e.g. an averaged
+            2.5); round such ratings first.
+    """
+    a, b = _paired(a, b)
+    if len(a) < 2:
+        return float("nan")
+
+    index = {level: i for i, level in enumerate(levels)}
+    unknown = sorted({x for x in list(a) + list(b) if x not in index})
+    if unknown:
+        raise ValueError(f"Ratings {unknown} are not rating levels {list(levels)}")
+    k = len(levels)
+
+    def weight(i, j):
+        if weights == "linear":
+            return abs(i - j) / (k - 1)
+        if weights == "quadratic":
+            return ((i - j) / (k - 1)) ** 2
+        return float(i != j)
+
+    n = len(a)
+    observed = Counter((index[x], index[y]) for x, y in zip(a, b))
+    marg_a = Counter(index[x] for x in a)
+    marg_b = Counter(index[y] for y in b)
+
+    disagreement_obs = sum(weight(i, j) * c for (i, j), c in observed.items()) / n
+    disagreement_exp = sum(
+        weight(i, j) * marg_a[i] * marg_b[j] for i in range(k) for j in range(k)
+    ) / (n * n)
+    if disagreement_exp == 0:
+        return 1.0
+    return 1 - disagreement_obs / disagreement_exp
+
+
+def fleiss_kappa(ratings: Sequence[Sequence], levels: Sequence = RATING_LEVELS) -> float:
+    """
+    Fleiss' kappa for any number of raters.
+
+    Args:
+        ratings: One list per item with the ratings of every rater. Items
+            with fewer than two ratings are ignored; the formula assumes
+            the same number of ratings per item, so items are truncated to
+            the smallest count present.
+
+    Returns:
+        Kappa; `nan` when there is not enough data.
+    """
+    items = [[r for r in item if r is not None] for item in ratings]
+    items = [item for item in items if len(item) >= 2]
+    if not items:
+        return float("nan")
+    m = min(len(item) for item in items)
+    items = [item[:m] for item in items]
+
+    n_items = len(items)
+    counts = [Counter(item) for item in items]
+    p_items = [(sum(c * c for c in count.values()) - m) / (m * (m - 1)) for count in counts]
+    p_bar = sum(p_items) / n_items
+
+    totals = Counter()
+    for count in counts:
+        totals.update(count)
+    p_e = sum((totals[level] / (n_items * m)) ** 2 for level in levels)
+    if p_e == 1:
+        return 1.0
+    return (p_bar - p_e) / (1 - p_e)
+
+
+def krippendorff_alpha(ratings: Sequence[Sequence], level: str = "ordinal") -> float:
+    """
+    Krippendorff's alpha, tolerant of missing ratings.
+
+    Args:
+        ratings: One list per item with the ratings of every rater
+            (`None` for missing).
+        level: "nominal", "ordinal" or "interval" measurement level.
+
+    Returns:
+        Alpha; 1 is perfect agreement, 0 chance level; `nan` without
+        pairable data.
+    """
+    units = [[r for r in unit if r is not None] for unit in ratings]
+    units = [unit for unit in units if len(unit) >= 2]
+    if not units:
+        return float("nan")
+
+    # coincidence matrix
+    coincidences: Counter = Counter()
+    for unit in units:
+        m = len(unit)
+        for i, j in itertools.permutations(range(m), 2):
+            coincidences[(unit[i], unit[j])] += 1 / (m - 1)
+
+    values = sorted({v for pair in coincidences for v in pair})
+    n_v = {v: sum(c for (x, _), c in coincidences.items() if x == v) for v in values}
+    n = sum(n_v.values())
+    if n <= 1 or len(values) == 1:
+        return 1.0
+
+    def delta(c, k):
+        if level == "nominal":
+            return float(c != k)
+        if level == "interval":
+            return float(c - k) ** 2
+        lo, hi = sorted((c, k))
+        between = sum(n_v[g] for g in values if lo <= g <= hi)
+        return (between - (n_v[c] + n_v[k]) / 2) ** 2
+
+    observed = sum(c * delta(x, y) for (x, y), c in coincidences.items())
+    expected = sum(n_v[x] * n_v[y] * delta(x, y) for x in values for y in values) / (n - 1)
+    if expected == 0:
+        return 1.0
+    return 1 - observed / expected
+
+
+def _ranks(values: Sequence[float]) -> List[float]:
+    order = sorted(range(len(values)), key=lambda i: values[i])
+    ranks = [0.0] * len(values)
+    i = 0
+    while i < len(order):
+        j = i
+        while j + 1 < len(order) and values[order[j + 1]] == values[order[i]]:
+            j += 1
+        for k in range(i, j + 1):
+            ranks[order[k]] = (i + j) / 2 + 1
+        i = j + 1
+    return ranks
+
+
+def spearman(a: Sequence, b: Sequence) -> float:
+    """
+    Spearman rank correlation with average ranks for ties.
+
+    Returns:
+        Rho in [-1, 1]; `nan` when either side is constant or fewer than
+        two items are paired.
+    """
+    a, b = _paired(a, b)
+    if len(a) < 2:
+        return float("nan")
+    ra, rb = _ranks(a), _ranks(b)
+    mean_a, mean_b = sum(ra) / len(ra), sum(rb) / len(rb)
+    cov = sum((x - mean_a) * (y - mean_b) for x, y in zip(ra, rb))
+    var_a = sum((x - mean_a) ** 2 for x in ra)
+    var_b = sum((y - mean_b) ** 2 for y in rb)
+    if var_a == 0 or var_b == 0:
+        return float("nan")
+    return cov / math.sqrt(var_a * var_b)
+
+
+def _mean(values: List[float]) -> float:
+    values = [v for v in values if not math.isnan(v)]
+    return sum(values) / len(values) if values else float("nan")
+
+
+def agreement_report(
+        ratings: Dict[str, Dict[str, List]],
+        human: Dict[str, List] | None = None,
+        criteria: Sequence[str] = CRITERIA
+        ) -> Dict[str, Any]:
+    """
+    Agreement between judges, and of each judge with human labels.
+
+    Args:
+        ratings: Judge name to criterion to ratings (one per sample, `None`
+            when missing).
+        human: Optional criterion to human ratings for the same samples.
+        criteria: Criteria to report.
+
+    Returns:
+        Criterion to `{"inter_judge": {...}, "vs_human": {judge: {...}}}`.
+        Inter-judge stats are the mean pairwise weighted Cohen's kappa and
+        Spearman, Fleiss' kappa and ordinal Krippendorff's alpha. Per-judge
+        human stats are weighted kappa, Spearman, exact agreement, mean
+        absolute error and bias (mean judge - human, positive when the
+        judge is lenient), which is what to watch for drift.
+    """
+
+    report: Dict[str, Any] = {}
+    judges = sorted(ratings)
+    for criterion in criteria:
+        columns = {j: ratings[j].get(criterion, []) for j in judges}
+        n_items = max((len(c) for c in columns.values()), default=0)
+        units = [[columns[j][i] if i < len(columns[j]) else None for j in judges] for i in range(n_items)]
+
+        entry: Dict[str, Any] = {}
+        if len(judges) >= 2:
+            pairs = list(itertools.combinations(judges, 2))
+            entry["inter_judge"] = {
+                "cohen_kappa": _mean([cohen_kappa(columns[x], columns[y]) for x, y in pairs]),
+                "spearman": _mean([spearman(columns[x], columns[y]) for x, y in pairs]),
+                "fleiss_kappa": fleiss_kappa(units),
+                "krippendorff_alpha": krippendorff_alpha(units, level="ordinal"),
+            }
+
+        if human and criterion in human:
+            entry["vs_human"] = {}
+            for judge in judges:
+                j, h = _paired(columns[judge], human[criterion])
+                entry["vs_human"][judge] = {
+                    "n": len(j),
+                    "cohen_kappa": cohen_kappa(j, h),
+                    "spearman": spearman(j, h),
+                    "exact_agreement": sum(x == y for x, y in zip(j, h)) / len(j) if j else float("nan"),
+                    "mae": sum(abs(x - y) for x, y in zip(j, h)) / len(j) if j else float("nan"),
+                    "bias": sum(x - y for x, y in zip(j, h)) / len(j) if j else float("nan"),
+                }
+        report[criterion] = entry
+    return report
+
+
+def build_judges(
+        specs: Sequence[Dict[str, str]],
+        prompt_config,
+        execution,
+        output_format: str = "json"
+        ) -> Dict[str, LLMJudge]:
+    """
+    Build one `LLMJudge` per `{"provider": ..., "model": ...}` spec.
+
+    Judges are named `provider:model` (or just `provider` without a model).
+    Their clients are wrapped with `executor.guard_client`, so judges of one
+    provider share its rate limit.
+
+    Args:
+        specs: Judge specs, e.g. `EvalConfig.calibration_judges`.
+        prompt_config: `PromptConfig` providing the judge template.
+        execution: `ExecutionConfig` with retry, timeout and rate-limit settings.
+        output_format: "json" or "text" judge output.
+    """
+    judges = {}
+    for spec in specs:
+        provider, model = spec["provider"], spec.get("model")
+        judges[f"{provider}:{model}" if model else provider] = LLMJudge(
+            client=guard_client(
+                get_llm_client(provider=provider, model_name=model),
+                provider=provider,
+                execution=execution,
+            ),
+            template=prompt_config.judge_template,
+            json_instructions=prompt_config.judge_json_instructions,
+            output_format=output_format,
+        )
+    return judges
+
+
+def score_with_judges(
+        samples: Sequence[Sample],
+        judges: Dict[str, Any],
+        repeats: int = 1
+        ) -> Dict[str, Dict[str, List]]:
+    """
+    Score `samples` with every judge, optionally several times each.
+
+    Repeats bypass the judge cache and are reported as separate raters
+    (`name#1`, `name#2`, ...), which measures a judge's self-consistency.
+
+    Args:
+        samples: Samples with question, answer and contexts.
+        judges: Judge name to `LLMJudge`.
+        repeats: Number of independent scorings per judge.
+
+    Returns:
+        Rater name to criterion to ratings, ready for `agreement_report`.
+    """
+
+    ratings: Dict[str, Dict[str, List]] = {}
+    for name, judge in judges.items():
+        for r in range(repeats):
+            rater = name if repeats == 1 else f"{name}#{r + 1}"
+            results: List[JudgeResult] = [
+                judge.judge(s.question, s.answer, s.contexts, use_cache=False) for s in samples
+            ]
+            ratings[rater] = {c: [res.scores.get(c) for res in results] for c in CRITERIA}
+    return ratings
+
+
+def load_calibration_set(path: str) -> tuple:
+    """
+    Load a human-labeled calibration set.
+
+    Each JSONL line holds `question`, `answer`, `contexts` (list of strings
+    or context dicts) and `human`, a criterion to 1-5 score mapping.
+
+    Returns:
+        `(samples, human)` where `human` maps criterion to ratings.
+    """
+    samples, human = [], {c: [] for c in CRITERIA}
+    with open(path, "r", encoding="utf-8") as f:
+        for line in f:
+            if not line.strip():
+                continue
+            row = json.loads(line)
+            contexts = [c if isinstance(c, dict) else {"text": c} for c in row.get("contexts", [])]
+            samples.append(Sample(question=row["question"], answer=row["answer"], contexts=contexts))
+            for c in CRITERIA:
+                human[c].append(row.get("human", {}).get(c))
+    return samples, human
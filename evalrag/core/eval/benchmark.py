@@ -0,0 +1,300 @@
+# evalrag/core/eval/benchmark.py
+
+"""
+Retrieval quality of chunking strategies on one corpus and dataset.
+
+Every configuration of a grid (chunker, chunk size, overlap) gets its own
+temporary FAISS index built with `Ingestion`, and the dataset questions
+are retrieved against it. Gold chunk ids of a dataset only exist under the
+chunking it was generated with, so a retrieved chunk is matched against
+the item's gold context text instead (falling back to its gold document
+ids).
+"""
+
+import re
+import shutil
+import tempfile
+import time
+from collections import Counter
+from dataclasses import dataclass, field, replace
+from pathlib import Path
+from typing import Any, Dict, List, Sequence
+
+from ..chunking import CHUNKER_REGISTRY
+from ..ingestion import Ingestion
+from ..rag import RAG
+from ..vectordb import FAISSVectorClient, QueryEmbedder
+from .retrieval import gold_relevance, ir_metrics
+from .runner import EvalItem, resolve_dataset
+
+_WORD = re.compile(r"\w+")
+
+
+@dataclass
+class ChunkingConfig:
+    """
+    One point of the benchmark grid.
+
+    Fields:
+        - `chunker`: Registered chunker name.
+        - `chunk_size` / `chunk_overlap`: Sizes passed to the chunker;
+          `None` for chunkers that ignore them (`sentence_window`).
+    """
+    chunker: str
+    chunk_size: int | None = None
+    chunk_overlap: int | None = None
+
+    @property
+    def label(self) -> str:
+        if self.chunk_size is None:
+            return self.chunker
+        return f"{self.chunker}/{self.chunk_size}/{self.chunk_overlap}"
+
+
+@dataclass
+class BenchmarkResult:
+    """
+    Index statistics and retrieval metrics of one `ChunkingConfig`.
+
+    Fields:
+        - `config`: The chunking configuration.
+        - `n_chunks`: Chunks in the index.
+        - `index_bytes`: Size of the index directory on disk.
+        - `ingest_seconds`: Wall time to load, chunk, embed and index the
+          corpus.
+        - `n_items`: Dataset items scored (items with a gold context or
+          gold document ids).
+        - `metrics`: Mean `ir_metrics` over the items (`hit_rate@k`,
+          `recall@k`, `ndcg@k`, `mrr`, `map`).
+    """
+    config: ChunkingConfig
+    n_chunks: int = 0
+    index_bytes: int = 0
+    ingest_seconds: float = 0.0
+    n_items: int = 0
+    metrics: Dict[str, float] = field(default_factory=dict)
+
+
+def chunking_grid(
+        chunkers: Sequence[str],
+        chunk_sizes: Sequence[int],
+        chunk_overlaps: Sequence[int]
+        ) -> List[ChunkingConfig]:
+    """
+    Every combination of chunker, size and overlap, skipping overlaps not
+    smaller than the size; chunkers that ignore sizes appear once.
+
+    Raises:
+        KeyError: If a chunker is not registered.
+    """
+    grid = []
+    for name in chunkers:
+        if name not in CHUNKER_REGISTRY:
+            raise KeyError(f"Unknown chunker '{name}'. Registered: {sorted(CHUNKER_REGISTRY)}")
+        if not CHUNKER_REGISTRY[name].sized:
+            grid.append(ChunkingConfig(name))
+            continue
+        grid += [
+            ChunkingConfig(name, size, overlap)
+            for size in chunk_sizes for overlap in chunk_overlaps if overlap < size
+        ]
+    return grid
+
+
+def text_overlap(a: str, b: str) -> float:
+    """
+    Shared words of `a` and `b` over the word count of the shorter one, so
+    a chunk inside a passage and a passage inside a chunk both score 1.
+    """
+    words_a, words_b = Counter(_WORD.findall(a.lower())), Counter(_WORD.findall(b.lower()))
+    shorter = min(sum(words_a.values()), sum(words_b.values()))
+    return sum((words_a & words_b).values()) / shorter if shorter else 0.0
+
+
+def passage_relevance(contexts: List[Dict], item: EvalItem, min_overlap: float = 0.5) -> List[bool]:
+    """
+    Relevance flags of retrieved contexts for an item, independent of the
+    chunking.
+
+    With a gold context, the first context (of a gold document, when
+    known) whose `text_overlap` with it reaches `min_overlap` is the one
+    relevant result. Without one, contexts are matched on gold document ids.
+    """
+    gold_docs = item.gold_doc_ids
+    if not item.gold_context:
+        return gold_relevance(contexts, gold_doc_ids=gold_docs)
+    flags, found = [], False
+    for c in contexts:
+        relevant = (
+            not found
+            and (not gold_docs or c.get("doc_id") in gold_docs)
+            and text_overlap(c["text"], item.gold_context) >= min_overlap
+        )
+        found = found or relevant
+        flags.append(relevant)
+    return flags
+
+
+def _dir_size(path: Path) -> int:
+    return sum(p.stat().st_size for p in path.rglob("*") if p.is_file())
+
+
+class ChunkingBenchmark:
+    """
+    Compare chunking configurations by the retrieval metrics of a dataset.
+
+    Responsibilities:
+      - Build a temporary index of the corpus per configuration through
+        `Ingestion.indexing` / `Ingestion.sync_dir`, timing it.
+      - Retrieve every dataset question against each index and average
+        hit-rate@k, recall@k, nDCG@k, MRR and MAP.
+
+    Typical usage:
+      1. `benchmark = ChunkingBenchmark(settings.ingestion, k_values=[1, 5])`
+      2. `results = benchmark.run(["data/docs"], "testset@1.0.0", chunking_grid(...))`
+      3. `print(to_markdown_table(results, [1, 5]))`
+
+    Args:
+        config: `IngestionConfig` the configurations are applied to.
+        embeddings: Embeddings model shared by all indexes; the config
+            provider's model when omitted.
+        k_values: Cut-offs for the @k metrics; the largest is the number of
+            chunks retrieved.
+        min_overlap: `text_overlap` at which a chunk matches a gold context.
+        work_dir: Parent directory of the temporary indexes; the system
+            temporary directory when omitted.
+        keep_indexes: Keep the indexes instead of deleting them after
+            scoring.
+    """
+
+    def __init__(
+            self,
+            config,
+            embeddings=None,
+            k_values: Sequence[int] = (1, 3, 5, 10),
+            min_overlap: float = 0.5,
+            work_dir: str | None = None,
+            keep_indexes: bool = False
+            ) -> None:
+        self.config = config
+        self.embeddings = embeddings or Ingestion.get_embedding_model(provider=config.provider)
+        self.k_values = sorted(int(k) for k in k_values)
+        self.min_overlap = min_overlap
+        self.work_dir = work_dir
+        self.keep_indexes = keep_indexes
+
+    def build_index(self, chunking: ChunkingConfig, sources: Sequence[str], path: str) -> int:
+        """
+        Index `sources` (files, or directories synced recursively) at `path`
+        with the chunking of `chunking`.
+
+        Returns:
+            The number of chunks indexed.
+        """
+        ingestion = Ingestion(replace(
+            self.config,
+            chunker=chunking.chunker,
+            default_chunk_size=chunking.chunk_size or self.config.default_chunk_size,
+            default_chunk_overlap=chunking.chunk_overlap or 0,
+            vector_store_path=path,
+        ))
+        for source in sources:
+            if Path(source).is_dir():
+                ingestion.sync_dir(source, embeddings=self.embeddings)
+            else:
+                ingestion.indexing(filename=source, embeddings=self.embeddings, force=True)
+        state = ingestion.load_index_state(path)
+        return sum(len(entry.get("chunk_ids", [])) for entry in state.values())
+
+    def score(self, path: str, items: Sequence[EvalItem]) -> Dict[str, float]:
+        """
+        Mean IR metrics of `items` retrieved against the index at `path`.
+        """
+        rag = RAG(
+            vector_client=FAISSVectorClient.load(path, self.embeddings),
+            embedder=QueryEmbedder(self.embeddings),
+            llm_client=None,
+            top_k=self.k_values[-1],
+        )
+        totals: Dict[str, float] = {}
+        for item in items:
+            relevance = passage_relevance(rag.retrieve(item.question), item, self.min_overlap)
+            n_relevant = 1 if item.gold_context else len(set(item.gold_doc_ids))
+            for name, value in ir_metrics(relevance, n_relevant, self.k_values).items():
+                totals[name] = totals.get(name, 0.0) + value
+        return {name: total / len(items) for name, total in totals.items()} if items else {}
+
+    def run(
+            self,
+            sources: Sequence[str],
+            dataset: str,
+            grid: Sequence[ChunkingConfig],
+            split: str | None = None,
+            limit: int | None = None,
+            registry=None
+            ) -> List[BenchmarkResult]:
+        """
+        Build, time and score an index per grid configuration.
+
+        Args:
+            sources: Corpus files or directories the dataset was built from.
+            dataset: Dataset file or registry `name@version`.
+            grid: Configurations to compare, e.g. from `chunking_grid`.
+            split: Optional dataset split.
+            limit: Optional cap on the number of dataset items.
+            registry: Optional `DatasetRegistry`.
+
+        Returns:
+            One `BenchmarkResult` per configuration, in grid order.
+        """
+
+        items, _ = resolve_dataset(dataset, split, registry)
+        items = [i for i in items if i.gold_context or i.gold_doc_ids][:limit]
+
+        results = []
+        for chunking in grid:
+            path = Path(tempfile.mkdtemp(prefix="chunking-", dir=self.work_dir))
+            try:
+                started = time.perf_counter()
+                n_chunks = self.build_index(chunking, sources, str(path))
+                ingest_seconds = time.perf_counter() - started
+                results.append(BenchmarkResult(
+                    config=chunking,
+                    n_chunks=n_chunks,
+                    index_bytes=_dir_size(path),
+                    ingest_seconds=ingest_seconds,
+                    n_items=len(items),
+                    metrics=self.score(str(path), items) if n_chunks else {},
+                ))
+            finally:
+                if not self.keep_indexes:
+                    shutil.rmtree(path, ignore_errors=True)
+        return results
+
+
+def to_markdown_table(results: Sequence[BenchmarkResult], k_values: Sequence[int]) -> str:
+    """
+    Markdown table of the results, best MRR first.
+    """
+    k_values = sorted(int(k) for k in k_values)
+    header = ["chunker", "size", "overlap", "chunks", "index MB", "ingest s",
+              *[f"hit@{k}" for k in k_values], "MRR"]
+    lines = ["| " + " | ".join(header) + " |", "|" + "---|" * len(header)]
+
+    def fmt(value: Any) -> str:
+        return "-" if value is None else f"{value:.3f}"
+
+    for result in sorted(results, key=lambda r: r.metrics.get("mrr", -1.0), reverse=True):
+        config = result.config
+        row = [
+            config.chunker,
+            "-" if config.chunk_size is None else str(config.chunk_size),
+            "-" if config.chunk_overlap is None else str(config.chunk_overlap),
+            str(result.n_chunks),
+            f"{result.index_bytes / 1e6:.2f}",
+            f"{result.ingest_seconds:.1f}",
+            *[fmt(result.metrics.get(f"hit_rate@{k}")) for k in k_values],
+            fmt(result.metrics.get("mrr")),
+        ]
+        lines.append("| " + " | ".join(row) + " |")
+    return "\n".join(lines)
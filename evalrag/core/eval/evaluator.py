@@ -0,0 +1,152 @@
+# evalrag/core/eval/evaluator.py
+
+
+"""
+Evaluation of RAG system has tow main parts:
+    1. Retrieval:
+        1. Context Recall
+        2. Context Percision
+
+    2. Generation:
+        1. Faithfulness
+        2. Answer Relevancy
+"""
+
+"""
+Steps to evaluate RAG:
+    1. An evaluation dataset with question - answer couples (QA couples):
+        The evaluation dataset will be synthetically generated by an LLM 🤖, and questions will be filtered out by other LLMs 🤖
+    2. An evaluator to compute the accuracy of our system on the above evaluation dataset:
+        An LLM-as-a-judge agent 🤖 will then perform the evaluation on this synthetic dataset.
+
+"""
+
+
+from dataclasses import asdict  # noqa: E402
+from typing import List, Dict, Any  # noqa: E402
+
+from ..config import load_prompt_config  # noqa: E402
+from ..ingestion import Ingestion  # noqa: E402
+from ..llm import get_llm_client  # noqa: E402
+from .judge import LLMJudge  # noqa: E402
+from .metrics import Sample, MetricResult, build_metrics  # noqa: E402
+from .scoring import resolve_weights, composite_score, is_hallucination, aggregate_scores  # noqa: E402
+
+
+class Evaluator:
+
+    """
+    Evaluation helper for assessing RAG answers.
+
+    Responsibilities:
+      - Use a judge model to evaluate answers.
+      - Compute metrics like correctness, faithfulness, and relevance.
+      - Aggregate scores based on configured weights.
+    Typical usage:
+      1. `evaluate_answer(question, answer, contexts)` to get evaluation scores.
+      2. `summarize(results)` to get run-level composite and hallucination rate.
+      3. `score_sample(sample)` to run the configured metrics from the registry.
+    Args:
+        config: Configuration object or mapping used by evaluation routines.
+        judge_client: Optional LLM client used as the judge; defaults to the
+            client for `config.judge_provider`.
+        prompt_config: Optional `PromptConfig` providing the judge template.
+        embedder: Optional embeddings model shared by embedding-based metrics.
+        metrics: Optional list of registered metric names; defaults to
+            `config.metrics`.
+    """
+
+    def __init__(
+            self,
+            config,
+            judge_client=None,
+            prompt_config=None,
+            embedder=None,
+            metrics: List[str] | None = None
+            ) -> None:
+
+        """
+        Initialize the Evaluator.
+
+        Args:
+            config: Configuration object or mapping used by evaluation processes.
+            judge_client: Optional client exposing `generate(prompt, json_mode=False)`.
+            prompt_config: Optional `PromptConfig`; loaded from YAML when omitted.
+            embedder: Optional embeddings model for embedding-based metrics;
+                built from `config.embedding_provider` when a metric needs one.
+            metrics: Optional metric names overriding `config.metrics`.
+
+        The provided `config` is stored on the instance for later use by
+        other methods in this class.
+        """
+        self.config = config
+        self.prompt_config = prompt_config or load_prompt_config()
+        self.judge_client = judge_client or get_llm_client(
+            provider=config.judge_provider,
+            model_name=config.judge_model,
+        )
+        self.judge = LLMJudge(
+            client=self.judge_client,
+            template=self.prompt_config.judge_template,
+            json_instructions=self.prompt_config.judge_json_instructions,
+            output_format=config.judge_output_format,
+        )
+        self.weights = resolve_weights(config)
+        self.metrics = build_metrics(
+            metrics if metrics is not None else config.metrics,
+            params=config.metric_params,
+            judge=self.judge,
+            llm=self.judge_client,
+            embedder=embedder,
+            prompt_config=self.prompt_config,
+        )
+        if embedder is None and any(m.needs_embedder for m in self.metrics):
+            embedder = Ingestion.get_embedding_model(provider=config.embedding_provider)
+            for metric in self.metrics:
+                metric.embedder = embedder
+        self.embedder = embedder
+    
+    def score_sample(self, sample: Sample) -> Dict[str, MetricResult]:
+        """
+        Run every configured metric on `sample`.
+
+        Args:
+            sample: The `Sample` to score.
+
+        Returns:
+            Metric name to `MetricResult`, in `config.metrics` order.
+        """
+        return {metric.name: metric.score(sample) for metric in self.metrics}
+
+    def evaluate_answer(self, question: str, answer: str, contexts: List[Dict]) -> Dict[str, Any]:
+        """
+        Evaluate the given `answer` to `question` using `contexts`.
+        Args:
+            question: The user question text.
+            answer: The generated answer text.
+            contexts: List of context chunks used to generate the answer.
+        Returns:
+            A dict with `status`, `scores`, `rationales`, `feedback` and the
+            `raw` judge output, plus the weighted `composite` score in [0, 1]
+            and the `hallucination` flag. When `status` is "parse_error",
+            `scores` only holds the criteria that could be parsed and
+            `composite` may be `None`.
+        """
+        result = asdict(self.judge.judge(question=question, answer=answer, contexts=contexts))
+        result["composite"] = composite_score(result["scores"], self.weights)
+        result["hallucination"] = is_hallucination(
+            result["scores"], self.config.faithfulness_threshold
+        )
+        return result
+
+    def summarize(self, results: List[Dict[str, Any]]) -> Dict[str, Any]:
+        """
+        Aggregate `evaluate_answer` results into run-level numbers.
+
+        Args:
+            results: Per-answer dicts returned by `evaluate_answer`.
+
+        Returns:
+            See `scoring.aggregate_scores`.
+        """
+        return aggregate_scores(results)
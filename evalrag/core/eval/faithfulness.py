@@ -0,0 +1,86 @@
+# evalrag/core/eval/faithfulness.py
+
+from typing import Any, Dict, List
+
+from ..rag import RAG
+from .metrics import (
+    Metric,
+    MetricResult,
+    Sample,
+    register_metric,
+    METRIC_PARSE_ERROR,
+    METRIC_SKIPPED,
+)
+from .parsing import extract_list, parse_verdicts
+
+
+@register_metric
+class ClaimFaithfulnessMetric(Metric):
+    """
+    Claim-level faithfulness of an answer to its retrieved contexts.
+
+    The answer is decomposed into atomic claims by the LLM, then every claim
+    is checked against the contexts returned by `RAG.retrieve`. The score is
+    the fraction of supported claims; `details` lists every claim with its
+    verdict and reason, plus the unsupported claims on their own so
+    reviewers can see exactly what was hallucinated.
+
+    Answers without any factual claim (e.g. "I don't know") are skipped.
+    """
+
+    name = "claim_faithfulness"
+    required_inputs = ("answer", "contexts")
+
+    def extract_claims(self, question: str, answer: str) -> List[str]:
+        """
+        Decompose `answer` into atomic claims.
+        """
+        prompt = self.prompt_config.claim_extraction_template.format(
+            question=question,
+            answer=answer,
+        )
+        response = self.llm.generate(prompt, json_mode=True)
+        claims = extract_list(response["text"], "claims")
+        return [str(c).strip() for c in claims if str(c).strip()]
+
+    def verify_claims(self, claims: List[str], contexts: List[Dict]) -> Dict[int, Dict[str, Any]]:
+        """
+        Check every claim against `contexts` in a single LLM call.
+        """
+        prompt = self.prompt_config.claim_verification_template.format(
+            context_block=RAG.build_context_block(contexts),
+            claims="\n".join(f"{i}. {claim}" for i, claim in enumerate(claims, start=1)),
+        )
+        response = self.llm.generate(prompt, json_mode=True)
+        return parse_verdicts(response["text"], len(claims), id_key="claim", verdict_key="supported")
+
+    def compute(self, sample: Sample) -> MetricResult:
+        claims = self.extract_claims(sample.question, sample.answer)
+        if not claims:
+            return MetricResult(
+                name=self.name,
+                score=None,
+                status=METRIC_SKIPPED,
+                details={"reason": "answer contains no factual claims", "claims": []},
+            )
+
+        verdicts = self.verify_claims(claims, sample.contexts)
+        judged = [
+            {"claim": claim, "supported": verdicts[i]["verdict"], "reason": verdicts[i]["reason"]}
+            for i, claim in enumerate(claims, start=1)
+            if i in verdicts
+        ]
+        details = {
+            "claims": judged,
+            "unsupported_claims": [c["claim"] for c in judged if not c["supported"]],
+            "n_claims": len(claims),
+        }
+
+        if len(judged) < len(claims):
+            details["unverified_claims"] = [
+                claim for i, claim in enumerate(claims, start=1) if i not in verdicts
+            ]
+            return MetricResult(name=self.name, score=None, status=METRIC_PARSE_ERROR, details=details)
+
+        supported = sum(1 for c in judged if c["supported"])
+        return MetricResult(name=self.name, score=supported / len(claims), details=details)
@@ -0,0 +1,244 @@
+# evalrag/core/eval/gate.py
+
+"""
+Quality gate over evaluation runs.
+
+A policy file (`configs/policy.yaml`) defines named suites: the dataset to
+evaluate, thresholds on the run summary and limits on regressions against
+a baseline run. `check_policy` turns a run summary (and optional
+`RunComparison`) into pass/fail checks, rendered as JUnit XML and Markdown
+for CI.
+"""
+
+import math
+import xml.etree.ElementTree as ET
+from dataclasses import dataclass, field
+from typing import Any, Dict, List
+
+import yaml
+
+from ..config import CONFIG_FILE, _get
+
+
+@dataclass
+class GateCheck:
+    """
+    Outcome of a single policy check.
+
+    Fields:
+        - `name`: Check identifier, e.g. `judge.hallucination_rate <= 0.05`.
+        - `passed`: Whether the check holds.
+        - `value`: Observed value (`None` when missing from the run).
+        - `message`: Human-readable explanation.
+    """
+    name: str
+    passed: bool
+    value: Any = None
+    message: str = ""
+
+
+@dataclass
+class GateReport:
+    """
+    Result of gating one run against a suite policy.
+    """
+    suite: str
+    run_id: str
+    checks: List[GateCheck] = field(default_factory=list)
+    summary: Dict[str, Any] = field(default_factory=dict)
+    baseline_run_id: str | None = None
+
+    @property
+    def passed(self) -> bool:
+        return all(c.passed for c in self.checks)
+
+    @property
+    def failures(self) -> List[GateCheck]:
+        return [c for c in self.checks if not c.passed]
+
+
+def load_policy(path: str, suite: str, require_dataset: bool = True) -> Dict[str, Any]:
+    """
+    Load the policy of `suite` from a policy YAML file.
+
+    Top-level suite keys override those of the file's `defaults` section,
+    so shared thresholds only need to be declared once. The `baseline`
+    mapping is merged key by key instead: a suite setting only
+    `baseline.run_id` keeps the default regression limits.
+
+    Thresholds are resolved once here, so a misspelled config path fails
+    before the suite is evaluated rather than after.
+
+    Args:
+        path: Policy YAML file.
+        suite: Suite name under `suites`.
+        require_dataset: Fail when the suite has no `dataset` to evaluate;
+            unset when gating an existing run.
+
+    Raises:
+        KeyError: If the suite is not defined, or has no `dataset` while
+            one is required.
+        ValueError: If a threshold is not a number or a set config path.
+    """
+    with open(path, "r", encoding="utf-8") as f:
+        policy = yaml.safe_load(f) or {}
+    suites = policy.get("suites", {})
+    if suite not in suites:
+        raise KeyError(f"Unknown suite '{suite}'; defined suites: {', '.join(sorted(suites)) or 'none'}")
+
+    defaults = policy.get("defaults") or {}
+    merged = {**defaults, **(suites[suite] or {})}
+    if isinstance(defaults.get("baseline"), dict) and isinstance(merged.get("baseline"), dict):
+        merged["baseline"] = {**defaults["baseline"], **merged["baseline"]}
+    if require_dataset and not merged.get("dataset"):
+        raise KeyError(f"Suite '{suite}' has no dataset to evaluate")
+    for rule in merged.get("thresholds", []):
+        for bound in ("min", "max"):
+            if bound in rule:
+                resolve_threshold(rule[bound])
+    return merged
+
+
+def resolve_threshold(value: Any) -> float:
+    """
+    Turn a policy threshold into a number.
+
+    Numbers are used as is; strings are read as dotted paths into
+    `configs/core.yaml` (e.g. `eval.faithfulness_threshold`) so the gate and
+    the evaluator share one setting.
+
+    Raises:
+        ValueError: If the path is not set or the value is not a number.
+    """
+    resolved = value
+    if isinstance(value, str):
+        resolved = _get(CONFIG_FILE, value)
+        if resolved is None:
+            raise ValueError(f"Threshold '{value}' is not set in {CONFIG_FILE.name}")
+    try:
+        return float(resolved)
+    except (TypeError, ValueError):
+        raise ValueError(f"Threshold {value!r} is not a number") from None
+
+
+def summary_value(summary: Dict[str, Any], path: str) -> Any:
+    """
+    Read a dotted `path` from a run summary.
+
+    Keys may contain dots themselves (e.g. `metrics.ir.hit_rate@5`), so at
+    each level the longest matching key wins.
+    """
+    cur: Any = summary
+    rest = path
+    while rest:
+        if not isinstance(cur, dict):
+            return None
+        parts = rest.split(".")
+        for i in range(len(parts), 0, -1):
+            key = ".".join(parts[:i])
+            if key in cur:
+                cur, rest = cur[key], ".".join(parts[i:])
+                break
+        else:
+            return None
+    return cur
+
+
+def check_policy(policy: Dict[str, Any], summary: Dict[str, Any], comparison=None) -> List[GateCheck]:
+    """
+    Evaluate a suite policy against a run.
+
+    Args:
+        policy: Suite policy from `load_policy`. `thresholds` is a list of
+            `{metric, min, max}` entries on summary paths; `baseline` may set
+            `max_regressions` and `max_significant_regressions`.
+        summary: Run summary as written by `EvaluationRunner`.
+        comparison: Optional `RunComparison` of the run against the baseline.
+
+    Returns:
+        One `GateCheck` per threshold and regression limit.
+    """
+
+    checks: List[GateCheck] = []
+    for rule in policy.get("thresholds", []):
+        path = rule["metric"]
+        value = summary_value(summary, path)
+        for bound in ("min", "max"):
+            if bound not in rule:
+                continue
+            limit = resolve_threshold(rule[bound])
+            name = f"{path} {'>=' if bound == 'min' else '<='} {limit:g}"
+            if not isinstance(value, (int, float)) or isinstance(value, bool) or math.isnan(value):
+                checks.append(GateCheck(name, False, value, f"`{path}` is missing from the run summary"))
+                continue
+            passed = value >= limit if bound == "min" else value <= limit
+            checks.append(GateCheck(name, passed, value, f"{path} = {value:.4g}"))
+
+    baseline = policy.get("baseline") or {}
+    if comparison is not None:
+        if "max_regressions" in baseline:
+            limit = int(baseline["max_regressions"])
+            n = len(comparison.regressions)
+            checks.append(GateCheck(
+                f"regressions vs {comparison.base_run_id} <= {limit}", n <= limit, n,
+                f"{n} item-level regressions",
+            ))
+        if "max_significant_regressions" in baseline:
+            limit = int(baseline["max_significant_regressions"])
+            significant = sorted(
+                metric for metric, report in comparison.significance.items()
+                if report["significant"] and report["mean_delta"] < 0
+            )
+            checks.append(GateCheck(
+                f"significant regressions vs {comparison.base_run_id} <= {limit}",
+                len(significant) <= limit, len(significant),
+                f"significantly worse: {', '.join(significant) or 'none'}",
+            ))
+    return checks
+
+
+def to_junit_xml(report: GateReport) -> str:
+    """
+    Render a gate report as a JUnit XML test suite, one test case per check.
+    """
+    suite = ET.Element(
+        "testsuite",
+        name=f"evalrag.{report.suite}",
+        tests=str(len(report.checks)),
+        failures=str(len(report.failures)),
+        errors="0",
+    )
+    for check in report.checks:
+        case = ET.SubElement(suite, "testcase", classname=f"evalrag.{report.suite}", name=check.name)
+        if not check.passed:
+            failure = ET.SubElement(case, "failure", message=check.message)
+            failure.text = f"run {report.run_id}: {check.message}"
+    return '<?xml version="1.0" encoding="UTF-8"?>\n' + ET.tostring(suite, encoding="unicode")
+
+
+def to_markdown(report: GateReport, comparison=None) -> str:
+    """
+    Render a gate report as a Markdown summary for CI job pages and PRs.
+    """
+    status = "✅ passed" if report.passed else "❌ failed"
+    lines = [
+        f"## EvalRAG gate `{report.suite}`: {status}",
+        "",
+        f"Run `{report.run_id}`" + (f" vs baseline `{report.baseline_run_id}`" if report.baseline_run_id else ""),
+        "",
+        "| Check | Value | Result |",
+        "| --- | --- | --- |",
+    ]
+    for check in report.checks:
+        value = f"{check.value:.4g}" if isinstance(check.value, float) else str(check.value)
+        lines.append(f"| `{check.name}` | {value} | {'pass' if check.passed else 'FAIL'} |")
+
+    if comparison is not None and comparison.metric_deltas:
+        lines += ["", "| Metric | Base | Candidate | Delta | p (perm.) |", "| --- | --- | --- | --- | --- |"]
+        for metric, d in comparison.metric_deltas.items():
+            p = comparison.significance.get(metric, {}).get("permutation_p")
+            lines.append(
+                f"| `{metric}` | {d['base_mean']:.4g} | {d['candidate_mean']:.4g} | "
+                f"{d['delta']:+.4g} | {'-' if p is None else f'{p:.3g}'} |"
+            )
+    return "\n".join(lines) + "\n"
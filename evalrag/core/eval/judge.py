@@ -0,0 +1,231 @@
+# evalrag/core/eval/judge.py
+
+import re
+import threading
+from collections import OrderedDict
+from dataclasses import dataclass, field
+from typing import Dict, List
+
+from ..rag import RAG
+from .parsing import extract_json
+
+CRITERIA = ("correctness", "faithfulness", "relevance")
+
+JUDGE_OK = "ok"
+JUDGE_PARSE_ERROR = "parse_error"
+
+
+@dataclass
+class JudgeResult:
+    """
+    Outcome of a single judge call.
+
+    Fields:
+        - `status`: `JUDGE_OK` when every criterion was parsed, otherwise
+          `JUDGE_PARSE_ERROR`. Scores are never defaulted.
+        - `scores`: Criterion name to 1-5 score, only for parsed criteria.
+        - `rationales`: Criterion name to the judge's explanation.
+        - `feedback`: Overall explanation, if the judge gave one.
+        - `raw`: The unparsed judge output.
+    """
+    status: str
+    scores: Dict[str, int] = field(default_factory=dict)
+    rationales: Dict[str, str] = field(default_factory=dict)
+    feedback: str = ""
+    raw: str = ""
+
+
+def _valid_score(value) -> int | None:
+    """
+    The 1-5 score in `value`; `None` when it is missing, out of range or not
+    a whole number (a 4.7 is a parse error, not a 4).
+    """
+    try:
+        score = float(value)
+    except (TypeError, ValueError):
+        return None
+    return int(score) if score.is_integer() and 1 <= score <= 5 else None
+
+
+def parse_judge_json(text: str, criteria=CRITERIA) -> JudgeResult | None:
+    """
+    Parse structured judge output.
+
+    Accepts either `{"correctness": 4, ...}` or
+    `{"correctness": {"score": 4, "rationale": "..."}, ...}`, with keys
+    matched case-insensitively.
+
+    Returns:
+        A `JudgeResult`, or `None` if `text` holds no JSON object.
+    """
+
+    payload = extract_json(text)
+    if not isinstance(payload, dict):
+        return None
+    payload = {str(k).lower(): v for k, v in payload.items()}
+
+    scores, rationales = {}, {}
+    for name in criteria:
+        value = payload.get(name)
+        if isinstance(value, dict):
+            score = _valid_score(value.get("score"))
+            rationale = value.get("rationale") or value.get("explanation") or ""
+        else:
+            score = _valid_score(value)
+            rationale = payload.get(f"{name}_rationale", "")
+        if score is not None:
+            scores[name] = score
+            rationales[name] = str(rationale).strip()
+
+    return JudgeResult(
+        status=JUDGE_OK if len(scores) == len(criteria) else JUDGE_PARSE_ERROR,
+        scores=scores,
+        rationales=rationales,
+        feedback=str(payload.get("feedback", "")).strip(),
+        raw=text,
+    )
+
+
+def parse_judge_text(text: str, criteria=CRITERIA) -> JudgeResult:
+    """
+    Tolerant regex parser for free-form judge output.
+
+    Recognises lines such as `Correctness: 4 - ...`, `**Faithfulness**: 5/5`,
+    `Faithfulness (1-5): 4` or `Relevance (score 3): ...`; a score right
+    after the colon wins over digits before it (such as a scale). The rest
+    of the line, or the next line when it is empty, is taken as the
+    rationale.
+    """
+
+    lines = (text or "").splitlines()
+    scores, rationales = {}, {}
+    score = r"([1-5])(?:\s*/\s*5|\s+out of\s+5)?(?![0-9.])[\s)\]*:.,\-–—]*(.*)"
+    for name in criteria:
+        after_colon = re.compile(rf"\b{name}\b[^:\n]{{0,30}}:[\s*_]*{score}", re.IGNORECASE)
+        anywhere = re.compile(rf"\b{name}\b[^0-9\n]{{0,20}}?{score}", re.IGNORECASE)
+        for i, line in enumerate(lines):
+            match = after_colon.search(line) or anywhere.search(line)
+            if not match:
+                continue
+            scores[name] = int(match.group(1))
+            rationale = match.group(2).strip()
+            if not rationale and i + 1 < len(lines):
+                rationale = lines[i + 1].strip()
+            rationales[name] = rationale
+            break
+
+    return JudgeResult(
+        status=JUDGE_OK if len(scores) == len(criteria) else JUDGE_PARSE_ERROR,
+        scores=scores,
+        rationales=rationales,
+        raw=text or "",
+    )
+
+
+def parse_judge_output(text: str, criteria=CRITERIA) -> JudgeResult:
+    """
+    Parse judge output, trying JSON first and the regex parser second.
+
+    Args:
+        text: Raw judge output.
+        criteria: Criterion names expected in the output.
+
+    Returns:
+        The most complete `JudgeResult` either parser could produce.
+    """
+
+    structured = parse_judge_json(text, criteria)
+    if structured is not None and structured.status == JUDGE_OK:
+        return structured
+
+    fallback = parse_judge_text(text, criteria)
+    if structured is not None and len(structured.scores) >= len(fallback.scores):
+        return structured
+    return fallback
+
+
+class LLMJudge:
+    """
+    LLM-as-a-judge wrapper around a provider client.
+
+    Responsibilities:
+      - Render the judge template with question, answer and context block.
+      - Request structured JSON output when `output_format` is "json".
+      - Parse per-criterion scores and rationales into a `JudgeResult`.
+      - Cache recent results so several metrics reading different criteria
+        of the same sample trigger a single judge call. The cache is shared
+        by the runner's worker threads and guarded by a lock.
+
+    Args:
+        client: Object exposing `generate(prompt, json_mode=False)`.
+        template: Judge prompt template with `{question}`, `{answer}` and
+            `{context_block}` placeholders.
+        json_instructions: Text appended to the prompt in JSON mode.
+        output_format: "json" for structured output, "text" for free-form.
+        cache_size: Number of recent prompts whose results are kept.
+    """
+
+    def __init__(
+            self,
+            client,
+            template: str,
+            json_instructions: str = "",
+            output_format: str = "json",
+            cache_size: int = 256
+            ) -> None:
+        self.client = client
+        self.template = template
+        self.json_instructions = json_instructions
+        self.output_format = output_format
+        self.cache_size = cache_size
+        self._cache: OrderedDict[str, JudgeResult] = OrderedDict()
+        self._cache_lock = threading.Lock()
+
+    def build_prompt(self, question: str, answer: str, contexts: List[Dict]) -> str:
+        """
+        Render the judge prompt for a single answer.
+        """
+        prompt = self.template.format(
+            question=question,
+            answer=answer,
+            context_block=RAG.build_context_block(contexts),
+        )
+        if self.output_format == "json" and self.json_instructions:
+            prompt = f"{prompt}\n\n{self.json_instructions}"
+        return prompt
+
+    def judge(
+            self,
+            question: str,
+            answer: str,
+            contexts: List[Dict],
+            use_cache: bool = True
+            ) -> JudgeResult:
+        """
+        Score `answer` for `question` against `contexts`.
+
+        Args:
+            use_cache: Reuse a cached result for an identical prompt; disable
+                to draw an independent sample from the judge.
+
+        Returns:
+            A `JudgeResult`; check `status` before using `scores`.
+        """
+        prompt = self.build_prompt(question, answer, contexts)
+        if use_cache:
+            with self._cache_lock:
+                cached = self._cache.get(prompt)
+                if cached is not None:
+                    self._cache.move_to_end(prompt)
+                    return cached
+
+        # the judge call itself runs outside the lock so workers judge in parallel
+        response = self.client.generate(prompt, json_mode=self.output_format == "json")
+        result = parse_judge_output(response["text"])
+
+        with self._cache_lock:
+            self._cache[prompt] = result
+            self._cache.move_to_end(prompt)
+            while len(self._cache) > self.cache_size:
+                self._cache.popitem(last=False)
+        return result
@@ -0,0 +1,207 @@
+# evalrag/core/eval/metrics.py
+
+from dataclasses import dataclass, field
+from typing import Any, Dict, List
+
+from .scoring import normalize_score
+
+METRIC_OK = "ok"
+METRIC_PARSE_ERROR = "parse_error"
+METRIC_SKIPPED = "skipped"
+
+
+@dataclass
+class Sample:
+    """
+    A single evaluation sample passed to metrics.
+
+    Fields:
+        - `question`: The user question.
+        - `answer`: The generated answer.
+        - `contexts`: Retrieved contexts in the shape returned by `RAG.retrieve`.
+        - `reference`: Optional reference (ground-truth) answer.
+        - `gold_doc_ids` / `gold_chunk_ids`: Optional ids of the source
+          documents/chunks the question was generated from.
+        - `metadata`: Free-form extra fields from the dataset.
+    """
+    question: str
+    answer: str = ""
+    contexts: List[Dict] = field(default_factory=list)
+    reference: str | None = None
+    gold_doc_ids: List[str] = field(default_factory=list)
+    gold_chunk_ids: List[str] = field(default_factory=list)
+    metadata: Dict[str, Any] = field(default_factory=dict)
+
+
+@dataclass
+class MetricResult:
+    """
+    Output of `Metric.score`.
+
+    Fields:
+        - `name`: Name of the metric that produced the result.
+        - `score`: Value normalized to [0, 1], or `None` if not computed.
+        - `status`: `METRIC_OK`, `METRIC_PARSE_ERROR` or `METRIC_SKIPPED`.
+        - `details`: Metric-specific extras (raw scores, rationales, ...).
+    """
+    name: str
+    score: float | None
+    status: str = METRIC_OK
+    details: Dict[str, Any] = field(default_factory=dict)
+
+
+class Metric:
+    """
+    Base class for evaluation metrics.
+
+    Subclasses set `name` and `required_inputs` (names of `Sample` fields
+    that must be non-empty) and implement `compute`; metrics that embed text
+    set `needs_embedder` so the `Evaluator` can provide one. Shared resources such
+    as the judge or an embedder are injected by `build_metrics`; each
+    metric uses only the ones it needs.
+
+    Args:
+        judge: `LLMJudge` instance for judge-based metrics.
+        llm: Raw LLM client exposing `generate(prompt, json_mode=False)`.
+        embedder: Embeddings model exposing `embed_query`/`embed_documents`.
+        prompt_config: `PromptConfig` with the metric prompt templates.
+        **params: Metric-specific options.
+    """
+
+    name: str = ""
+    required_inputs: tuple = ("question",)
+    needs_embedder: bool = False
+
+    def __init__(self, judge=None, llm=None, embedder=None, prompt_config=None, **params) -> None:
+        self.judge = judge
+        self.llm = llm
+        self.embedder = embedder
+        self.prompt_config = prompt_config
+        self.params = params
+
+    def missing_inputs(self, sample: Sample) -> List[str]:
+        """
+        Return the required `Sample` fields that are empty.
+        """
+        return [name for name in self.required_inputs if not getattr(sample, name, None)]
+
+    def score(self, sample: Sample) -> MetricResult:
+        """
+        Score `sample`, skipping it when required inputs are missing.
+        """
+        missing = self.missing_inputs(sample)
+        if missing:
+            return MetricResult(
+                name=self.name,
+                score=None,
+                status=METRIC_SKIPPED,
+                details={"missing_inputs": missing},
+            )
+        return self.compute(sample)
+
+    def compute(self, sample: Sample) -> MetricResult:
+        raise NotImplementedError
+
+
+METRIC_REGISTRY: Dict[str, type] = {}
+
+
+def register_metric(cls):
+    """
+    Class decorator adding a `Metric` subclass to `METRIC_REGISTRY`.
+
+    Registered metrics can be selected by name from `eval.metrics` in
+    `core.yaml`, which is how domain-specific metrics are plugged in.
+    """
+    if not cls.name:
+        raise ValueError(f"{cls.__name__} must define a metric name")
+    METRIC_REGISTRY[cls.name] = cls
+    return cls
+
+
+def get_metric(name: str, params: Dict[str, Any] | None = None, **resources) -> Metric:
+    """
+    Instantiate the registered metric `name` with shared `resources` and
+    its metric-specific `params`.
+    """
+    if name not in METRIC_REGISTRY:
+        raise KeyError(f"Unknown metric '{name}'. Registered: {sorted(METRIC_REGISTRY)}")
+    return METRIC_REGISTRY[name](**resources, **(params or {}))
+
+
+def build_metrics(
+        names: List[str],
+        params: Dict[str, Dict[str, Any]] | None = None,
+        **resources
+        ) -> List[Metric]:
+    """
+    Instantiate every metric in `names`, in order.
+
+    Args:
+        names: Registered metric names.
+        params: Optional metric name to keyword options (`eval.metric_params`).
+        **resources: Shared resources passed to every metric.
+    """
+    params = params or {}
+    return [get_metric(name, params.get(name), **resources) for name in names]
+
+
+class JudgeCriterionMetric(Metric):
+    """
+    Metric reading one criterion from the LLM judge.
+
+    The judge rates all criteria in one call and caches the result, so
+    running correctness, faithfulness and relevance together costs a single
+    judge request per sample.
+    """
+
+    criterion: str = ""
+    required_inputs = ("question", "answer")
+
+    def compute(self, sample: Sample) -> MetricResult:
+        result = self.judge.judge(sample.question, sample.answer, sample.contexts)
+        if self.criterion not in result.scores:
+            return MetricResult(
+                name=self.name,
+                score=None,
+                status=METRIC_PARSE_ERROR,
+                details={"judge_status": result.status, "raw": result.raw},
+            )
+        raw_score = result.scores[self.criterion]
+        return MetricResult(
+            name=self.name,
+            score=normalize_score(raw_score),
+            details={
+                "raw_score": raw_score,
+                "rationale": result.rationales.get(self.criterion, ""),
+            },
+        )
+
+
+@register_metric
+class CorrectnessMetric(JudgeCriterionMetric):
+    """
+    Judge-rated factual correctness of the answer.
+    """
+    name = "correctness"
+    criterion = "correctness"
+
+
+@register_metric
+class FaithfulnessMetric(JudgeCriterionMetric):
+    """
+    Judge-rated faithfulness of the answer to the retrieved contexts.
+    """
+    name = "faithfulness"
+    criterion = "faithfulness"
+    required_inputs = ("question", "answer", "contexts")
+
+
+@register_metric
+class RelevanceMetric(JudgeCriterionMetric):
+    """
+    Judge-rated relevance of the retrieved contexts to the question.
+    """
+    name = "relevance"
+    criterion = "relevance"
+    required_inputs = ("question", "contexts")
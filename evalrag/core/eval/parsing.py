@@ -0,0 +1,176 @@
+# evalrag/core/eval/parsing.py
+
+import json
+import re
+from typing import Any, Dict, List
+
+_FENCE_RE = re.compile(r"```(?:json)?\s*(.*?)```", re.DOTALL | re.IGNORECASE)
+
+
+def extract_json(text: str) -> Any | None:
+    """
+    Extract the first JSON object or array embedded in an LLM response.
+
+    Handles Markdown code fences and leading/trailing prose around the
+    payload. Returns `None` when nothing parseable is found instead of
+    raising, so callers can fall back to a looser parser.
+
+    Args:
+        text: Raw model output.
+
+    Returns:
+        The decoded JSON value, or `None`.
+    """
+
+    if not text:
+        return None
+
+    candidates = [m.group(1) for m in _FENCE_RE.finditer(text)] + [text]
+    for candidate in candidates:
+        candidate = candidate.strip()
+        try:
+            return json.loads(candidate)
+        except ValueError:
+            pass
+
+        for open_ch, close_ch in (("{", "}"), ("[", "]")):
+            start = candidate.find(open_ch)
+            end = candidate.rfind(close_ch)
+            if start == -1 or end <= start:
+                continue
+            try:
+                return json.loads(candidate[start:end + 1])
+            except ValueError:
+                continue
+    return None
+
+
+_BULLET_RE = re.compile(r"^\s*(?:[-*•]|\d+[.)]|\(\d+\))\s+(.*\S)\s*$")
+
+
+def extract_list(text: str, key: str) -> List[Any]:
+    """
+    Extract a list from an LLM response.
+
+    Accepts a JSON array, a JSON object holding the list under `key`, or,
+    as a fallback, a Markdown bullet / numbered list.
+
+    Args:
+        text: Raw model output.
+        key: Object key to look under when the payload is a JSON object.
+
+    Returns:
+        The list of items (possibly empty).
+    """
+
+    payload = extract_json(text)
+    if isinstance(payload, dict):
+        payload = payload.get(key)
+    if isinstance(payload, list):
+        return payload
+
+    items = []
+    for line in (text or "").splitlines():
+        match = _BULLET_RE.match(line)
+        if match:
+            items.append(match.group(1))
+    return items
+
+
+def parse_bool(value: Any) -> bool | None:
+    """
+    Interpret yes/no style verdicts (`true`, "yes", "supported", "1", ...).
+
+    Returns:
+        The boolean verdict, or `None` if `value` is not recognised.
+    """
+
+    if isinstance(value, bool):
+        return value
+    if isinstance(value, (int, float)):
+        return bool(value)
+    text = str(value).strip().lower()
+    if text in ("true", "yes", "y", "1", "supported", "relevant", "attributed"):
+        return True
+    if text in ("false", "no", "n", "0", "unsupported", "not supported", "irrelevant", "not attributed"):
+        return False
+    return None
+
+
+_VERDICT_LINE_RE = re.compile(
+    r"^\W*(?:[a-z]+\s*)?(\d+)\W+(yes|no|true|false|supported|unsupported|not supported"
+    r"|relevant|irrelevant|attributed|not attributed)\b\W*(.*)$",
+    re.IGNORECASE,
+)
+
+
+def parse_verdicts(
+        text: str,
+        n_items: int,
+        id_key: str = "id",
+        verdict_key: str = "verdict",
+        list_key: str = "verdicts"
+        ) -> Dict[int, Dict[str, Any]]:
+    """
+    Parse per-item yes/no verdicts for a numbered list sent to the LLM.
+
+    Reads the JSON list under `list_key` first, e.g.
+    `{"verdicts": [{"claim": 1, "supported": true, "reason": "..."}]}`, and
+    falls back to lines such as `1. yes - stated in the second chunk`.
+
+    Args:
+        text: Raw model output.
+        n_items: Number of items that were sent; out-of-range ids are dropped.
+        id_key: Key holding the 1-based item number in each JSON entry.
+        verdict_key: Key holding the verdict in each JSON entry.
+        list_key: Key holding the list when the payload is a JSON object.
+
+    Returns:
+        1-based item number to `{"verdict": bool, "reason": str}`.
+    """
+
+    verdicts: Dict[int, Dict[str, Any]] = {}
+    for i, item in enumerate(extract_list(text, list_key), start=1):
+        if not isinstance(item, dict):
+            continue
+        try:
+            number = int(item.get(id_key, i))
+        except (TypeError, ValueError):
+            number = i
+        verdict = parse_bool(item.get(verdict_key, item.get("verdict")))
+        if verdict is not None and 1 <= number <= n_items:
+            verdicts[number] = {"verdict": verdict, "reason": str(item.get("reason", "")).strip()}
+
+    if verdicts:
+        return verdicts
+
+    for line in (text or "").splitlines():
+        match = _VERDICT_LINE_RE.match(line)
+        if not match:
+            continue
+        number = int(match.group(1))
+        verdict = parse_bool(match.group(2))
+        if verdict is not None and 1 <= number <= n_items:
+            verdicts[number] = {"verdict": verdict, "reason": match.group(3).strip()}
+    return verdicts
+
+
+# terminal punctuation followed by a capital or digit, optionally quoted/bracketed
+_SENTENCE_END = r"(?<=[.!?])\s+(?=[\"'(\[]?[A-Z0-9])"
+_SENTENCE_RE = re.compile(rf"{_SENTENCE_END}|\n+")
+_PARAGRAPH_RE = re.compile(rf"{_SENTENCE_END}|\n{{2,}}")
+
+
+def split_sentences(text: str, line_breaks: bool = True) -> List[str]:
+    """
+    Split `text` into sentences on terminal punctuation and line breaks.
+
+    Args:
+        text: Text to split.
+        line_breaks: Whether every line break ends a sentence, as in
+            reference answers written as lists. When `False` only blank
+            lines do, so hard-wrapped document text keeps its sentences
+            whole (chunkers).
+    """
+    pattern = _SENTENCE_RE if line_breaks else _PARAGRAPH_RE
+    return [s.strip() for s in pattern.split(text or "") if s and s.strip()]
@@ -0,0 +1,61 @@
+# evalrag/core/eval/relevancy.py
+
+from .metrics import Metric, MetricResult, Sample, register_metric, METRIC_PARSE_ERROR
+from .parsing import extract_json, extract_list, parse_bool
+from .similarity import cosine_similarity, embed_texts
+
+
+@register_metric
+class AnswerRelevancyMetric(Metric):
+    """
+    Answer relevancy via reverse question generation.
+
+    The LLM writes `n_questions` questions that the answer would respond to;
+    these are embedded together with the original question and the score is
+    their mean cosine similarity to it. Evasive or off-topic answers yield
+    questions unlike the original one. Answers the LLM marks as
+    noncommittal score 0 when `penalize_noncommittal` is set.
+
+    Params:
+        n_questions: Number of candidate questions to generate.
+        penalize_noncommittal: Zero the score of evasive answers.
+    """
+
+    name = "answer_relevancy"
+    required_inputs = ("question", "answer")
+    needs_embedder = True
+
+    def __init__(self, n_questions: int = 3, penalize_noncommittal: bool = True, **kwargs) -> None:
+        super().__init__(**kwargs)
+        self.n_questions = n_questions
+        self.penalize_noncommittal = penalize_noncommittal
+
+    def compute(self, sample: Sample) -> MetricResult:
+        prompt = self.prompt_config.reverse_question_template.format(
+            n=self.n_questions,
+            answer=sample.answer,
+        )
+        text = self.llm.generate(prompt, json_mode=True)["text"]
+        questions = [str(q).strip() for q in extract_list(text, "questions") if str(q).strip()]
+        if not questions:
+            return MetricResult(name=self.name, score=None, status=METRIC_PARSE_ERROR, details={"raw": text})
+
+        payload = extract_json(text)
+        noncommittal = bool(parse_bool(payload.get("noncommittal"))) if isinstance(payload, dict) else False
+
+        vectors = embed_texts(self.embedder, [sample.question] + questions)
+        similarities = [cosine_similarity(vectors[0], v) for v in vectors[1:]]
+        score = sum(similarities) / len(similarities)
+        if noncommittal and self.penalize_noncommittal:
+            score = 0.0
+
+        return MetricResult(
+            name=self.name,
+            score=score,
+            details={
+                "generated_questions": [
+                    {"question": q, "similarity": sim} for q, sim in zip(questions, similarities)
+                ],
+                "noncommittal": noncommittal,
+            },
+        )
@@ -0,0 +1,295 @@
+# evalrag/core/eval/retrieval.py
+
+import math
+from typing import Dict, List, Sequence
+
+from ..rag import RAG
+from .metrics import (
+    Metric,
+    MetricResult,
+    Sample,
+    register_metric,
+    METRIC_PARSE_ERROR,
+    METRIC_SKIPPED,
+)
+from .parsing import parse_verdicts, split_sentences
+from .similarity import cosine_similarity, embed_texts
+
+MODE_LLM = "llm"
+MODE_EMBEDDING = "embedding"
+
+
+def average_precision(relevance: List[bool], n_relevant: int | None = None) -> float:
+    """
+    Rank-aware precision of a ranked list of relevance flags.
+
+    Sums precision@k over the ranks k holding a relevant item and divides
+    by the number of relevant items, so relevant chunks ranked first score
+    higher than the same chunks ranked last. Returns 0 when nothing is
+    relevant.
+
+    Args:
+        relevance: Relevance flag per retrieved item, in rank order.
+        n_relevant: Total number of relevant items (e.g. gold chunks). When
+            omitted, only the relevant items that were retrieved count.
+    """
+    hits, total = 0, 0.0
+    for k, relevant in enumerate(relevance, start=1):
+        if relevant:
+            hits += 1
+            total += hits / k
+    denominator = n_relevant if n_relevant is not None else hits
+    return total / denominator if denominator else 0.0
+
+
+def gold_relevance(
+        contexts: List[Dict],
+        gold_chunk_ids: Sequence[str] = (),
+        gold_doc_ids: Sequence[str] = ()
+        ) -> List[bool]:
+    """
+    Relevance flags of retrieved contexts against gold ids.
+
+    Chunk ids are matched when known, otherwise document ids. Each gold id
+    counts once: a second chunk of an already found gold document is not
+    relevant again, so recall and nDCG stay within [0, 1].
+
+    Returns:
+        One flag per context, in rank order.
+    """
+    key, gold = ("chunk_id", set(gold_chunk_ids)) if gold_chunk_ids else ("doc_id", set(gold_doc_ids))
+    found, flags = set(), []
+    for c in contexts:
+        value = c.get(key)
+        relevant = value in gold and value not in found
+        if relevant:
+            found.add(value)
+        flags.append(relevant)
+    return flags
+
+
+def hit_rate_at_k(relevance: List[bool], k: int) -> float:
+    """
+    1 if any of the top `k` results is relevant, else 0.
+    """
+    return float(any(relevance[:k]))
+
+
+def recall_at_k(relevance: List[bool], k: int, n_relevant: int) -> float:
+    """
+    Fraction of the `n_relevant` gold items found in the top `k` results.
+    """
+    return sum(relevance[:k]) / n_relevant if n_relevant else 0.0
+
+
+def reciprocal_rank(relevance: List[bool]) -> float:
+    """
+    1 / rank of the first relevant result, or 0 if none is relevant.
+    """
+    for rank, relevant in enumerate(relevance, start=1):
+        if relevant:
+            return 1.0 / rank
+    return 0.0
+
+
+def ndcg_at_k(relevance: List[bool], k: int, n_relevant: int) -> float:
+    """
+    Binary-relevance normalized discounted cumulative gain at `k`.
+    """
+    dcg = sum(1.0 / math.log2(rank + 1) for rank, rel in enumerate(relevance[:k], start=1) if rel)
+    ideal = sum(1.0 / math.log2(rank + 1) for rank in range(1, min(n_relevant, k) + 1))
+    return dcg / ideal if ideal else 0.0
+
+
+def ir_metrics(relevance: List[bool], n_relevant: int, k_values: Sequence[int]) -> Dict[str, float]:
+    """
+    Compute the classical IR metrics for one ranked result list.
+
+    Args:
+        relevance: Relevance flag per retrieved item, in rank order.
+        n_relevant: Number of gold items for the query.
+        k_values: Cut-offs for the @k metrics.
+
+    Returns:
+        A flat dict with `hit_rate@k`, `recall@k`, `ndcg@k` for every k,
+        plus `mrr` and `map` over the full result list.
+    """
+    values: Dict[str, float] = {}
+    for k in k_values:
+        values[f"hit_rate@{k}"] = hit_rate_at_k(relevance, k)
+        values[f"recall@{k}"] = recall_at_k(relevance, k, n_relevant)
+        values[f"ndcg@{k}"] = ndcg_at_k(relevance, k, n_relevant)
+    values["mrr"] = reciprocal_rank(relevance)
+    values["map"] = average_precision(relevance, n_relevant)
+    return values
+
+
+class ContextMetric(Metric):
+    """
+    Shared plumbing for the context precision/recall metrics.
+
+    Params:
+        mode: "llm" to ask the judge LLM for per-item verdicts, or
+            "embedding" for a cheap cosine-similarity approximation.
+        similarity_threshold: Cosine similarity at or above which an item
+            counts as relevant/attributed in embedding mode.
+    """
+
+    def __init__(self, mode: str = MODE_LLM, similarity_threshold: float = 0.5, **kwargs) -> None:
+        super().__init__(**kwargs)
+        if mode not in (MODE_LLM, MODE_EMBEDDING):
+            raise ValueError(f"Unknown {self.name} mode '{mode}'")
+        self.mode = mode
+        self.similarity_threshold = similarity_threshold
+        self.needs_embedder = mode == MODE_EMBEDDING
+
+    def similarity_matrix(self, queries: List[str], contexts: List[Dict]) -> List[List[float]]:
+        """
+        Cosine similarity of every query against every context text.
+        """
+        vectors = embed_texts(self.embedder, queries + [c["text"] for c in contexts])
+        query_vectors, context_vectors = vectors[:len(queries)], vectors[len(queries):]
+        return [[cosine_similarity(q, c) for c in context_vectors] for q in query_vectors]
+
+
+@register_metric
+class ContextPrecisionMetric(ContextMetric):
+    """
+    Rank-aware precision of the retrieved contexts.
+
+    Every chunk returned by `RAG.retrieve` is marked relevant or not to the
+    question (by the LLM, or by embedding similarity), and the flags are
+    combined with `average_precision` in retrieval order.
+    """
+
+    name = "context_precision"
+    required_inputs = ("question", "contexts")
+
+    def relevance_flags(self, sample: Sample) -> Dict[int, Dict]:
+        if self.mode == MODE_EMBEDDING:
+            similarities = self.similarity_matrix([sample.question], sample.contexts)[0]
+            return {
+                i: {"verdict": sim >= self.similarity_threshold, "similarity": sim}
+                for i, sim in enumerate(similarities, start=1)
+            }
+
+        prompt = self.prompt_config.context_precision_template.format(
+            question=sample.question,
+            contexts="\n\n".join(
+                f"{i}. {c['text'].strip()}" for i, c in enumerate(sample.contexts, start=1)
+            ),
+        )
+        response = self.llm.generate(prompt, json_mode=True)
+        return parse_verdicts(
+            response["text"], len(sample.contexts), id_key="context", verdict_key="relevant"
+        )
+
+    def compute(self, sample: Sample) -> MetricResult:
+        flags = self.relevance_flags(sample)
+        per_context = [
+            {
+                "rank": i,
+                "doc_id": c.get("doc_id"),
+                "chunk_id": c.get("chunk_id"),
+                "relevant": flags[i]["verdict"] if i in flags else None,
+                **{k: v for k, v in flags.get(i, {}).items() if k != "verdict"},
+            }
+            for i, c in enumerate(sample.contexts, start=1)
+        ]
+        details = {"mode": self.mode, "contexts": per_context}
+
+        if len(flags) < len(sample.contexts):
+            return MetricResult(name=self.name, score=None, status=METRIC_PARSE_ERROR, details=details)
+
+        relevance = [flags[i]["verdict"] for i in range(1, len(sample.contexts) + 1)]
+        return MetricResult(name=self.name, score=average_precision(relevance), details=details)
+
+
+@register_metric
+class ContextRecallMetric(ContextMetric):
+    """
+    Fraction of the reference answer covered by the retrieved contexts.
+
+    The reference answer is split into sentence-level statements and each
+    one is checked for attribution to the retrieved chunks (by the LLM, or
+    by its best embedding similarity to any chunk).
+    """
+
+    name = "context_recall"
+    required_inputs = ("reference", "contexts")
+
+    def attribution_flags(self, statements: List[str], contexts: List[Dict]) -> Dict[int, Dict]:
+        if self.mode == MODE_EMBEDDING:
+            matrix = self.similarity_matrix(statements, contexts)
+            return {
+                i: {"verdict": max(row) >= self.similarity_threshold, "similarity": max(row)}
+                for i, row in enumerate(matrix, start=1)
+            }
+
+        prompt = self.prompt_config.context_recall_template.format(
+            context_block=RAG.build_context_block(contexts),
+            statements="\n".join(f"{i}. {s}" for i, s in enumerate(statements, start=1)),
+        )
+        response = self.llm.generate(prompt, json_mode=True)
+        return parse_verdicts(
+            response["text"], len(statements), id_key="statement", verdict_key="attributed"
+        )
+
+    def compute(self, sample: Sample) -> MetricResult:
+        statements = split_sentences(sample.reference)
+        if not statements:
+            return MetricResult(
+                name=self.name,
+                score=None,
+                status=METRIC_SKIPPED,
+                details={"reason": "reference answer has no statements"},
+            )
+        flags = self.attribution_flags(statements, sample.contexts)
+        per_statement = [
+            {
+                "statement": s,
+                "attributed": flags[i]["verdict"] if i in flags else None,
+                **{k: v for k, v in flags.get(i, {}).items() if k != "verdict"},
+            }
+            for i, s in enumerate(statements, start=1)
+        ]
+        details = {"mode": self.mode, "statements": per_statement}
+
+        if len(flags) < len(statements):
+            return MetricResult(name=self.name, score=None, status=METRIC_PARSE_ERROR, details=details)
+
+        attributed = sum(1 for f in flags.values() if f["verdict"])
+        return MetricResult(name=self.name, score=attributed / len(statements), details=details)
+
+
+@register_metric
+class IRMetric(Metric):
+    """
+    Deterministic retrieval metrics against gold chunk/document ids.
+
+    Needs no LLM: the retrieved contexts are matched against the ids the
+    question was generated from, and hit-rate@k, recall@k, nDCG@k, MRR and
+    MAP are reported in `details` for every k in `k_values`. The headline
+    `score` is the MRR.
+
+    Params:
+        k_values: Cut-offs for the @k metrics.
+    """
+
+    name = "ir"
+    required_inputs = ("question",)
+
+    def __init__(self, k_values: Sequence[int] = (1, 3, 5, 10), **kwargs) -> None:
+        super().__init__(**kwargs)
+        self.k_values = sorted(int(k) for k in k_values)
+
+    def missing_inputs(self, sample: Sample) -> List[str]:
+        if sample.gold_chunk_ids or sample.gold_doc_ids:
+            return []
+        return ["gold_chunk_ids|gold_doc_ids"]
+
+    def compute(self, sample: Sample) -> MetricResult:
+        gold = sample.gold_chunk_ids or sample.gold_doc_ids
+        relevance = gold_relevance(sample.contexts, sample.gold_chunk_ids, sample.gold_doc_ids)
+        values = ir_metrics(relevance, len(set(gold)), self.k_values)
+        return MetricResult(name=self.name, score=values["mrr"], details=values)
@@ -0,0 +1,91 @@
+# evalrag/core/eval/robustness.py
+
+"""
+Metric drop of perturbed dataset items against their clean originals.
+
+Works on runs of datasets built by `datasets.perturbation.Perturber`, whose
+items carry `perturbation` (family name, or "clean") and `original_id` in
+their metadata.
+"""
+
+from typing import Any, Dict, Iterable, List
+
+# `perturbation` of the unmodified items
+CLEAN = "clean"
+
+
+def _flatten(aggregate: Dict[str, Any]) -> Dict[str, float]:
+    """
+    Numeric judge and metric means of an `aggregate_results` dict, keyed by
+    their dotted summary path (`judge.composite_mean`, `metrics.ir.mrr`, ...).
+    """
+    judge = aggregate.get("judge") or {}
+    values = {
+        "judge.composite_mean": judge.get("composite_mean"),
+        "judge.hallucination_rate": judge.get("hallucination_rate"),
+        **{f"judge.criteria.{k}": v for k, v in (judge.get("criteria") or {}).items()},
+        **{f"metrics.{k}": v for k, v in (aggregate.get("metrics") or {}).items()},
+    }
+    return {k: v for k, v in values.items() if v is not None}
+
+
+def _composite(record: Dict[str, Any]) -> float | None:
+    return (record.get("judge") or {}).get("composite")
+
+
+def robustness_report(
+        records: Iterable[Dict[str, Any]],
+        by_perturbation: Dict[str, Dict[str, Any]]
+        ) -> Dict[str, Dict[str, Any]]:
+    """
+    Per perturbation family, the drop of every aggregate against the clean
+    items.
+
+    Args:
+        records: Result records of the run.
+        by_perturbation: `aggregate_by(records, "perturbation")`.
+
+    Returns:
+        Family to:
+          - `n_items`: Variants evaluated.
+          - `drop`: Summary path to clean mean minus perturbed mean; positive
+            is a degradation, except for rates where lower is better
+            (`judge.hallucination_rate`), where a negative drop is.
+          - `paired_composite_drop` / `n_pairs`: Mean per-item drop of the
+            judge composite over variants whose clean item was scored too.
+          - `injection_success_rate`: For `prompt_injection`, share of
+            answers that repeated the injected canary.
+        Empty when the run has no clean items.
+    """
+
+    if CLEAN not in by_perturbation:
+        return {}
+    records = [r for r in records if r.get("status") == "ok"]
+    clean = _flatten(by_perturbation[CLEAN])
+    clean_composite = {
+        (r.get("metadata") or {}).get("original_id"): _composite(r)
+        for r in records if (r.get("metadata") or {}).get("perturbation") == CLEAN
+    }
+
+    report = {}
+    for family, aggregate in by_perturbation.items():
+        if family == CLEAN:
+            continue
+        perturbed = _flatten(aggregate)
+        variants = [r for r in records if (r.get("metadata") or {}).get("perturbation") == family]
+        diffs: List[float] = []
+        for record in variants:
+            base = clean_composite.get(record["metadata"].get("original_id"))
+            if base is not None and _composite(record) is not None:
+                diffs.append(base - _composite(record))
+        entry = {
+            "n_items": aggregate["n_items"],
+            "drop": {k: clean[k] - v for k, v in perturbed.items() if k in clean},
+            "paired_composite_drop": sum(diffs) / len(diffs) if diffs else None,
+            "n_pairs": len(diffs),
+        }
+        canaries = [(r["metadata"]["canary"], r.get("answer") or "") for r in variants if r["metadata"].get("canary")]
+        if canaries:
+            entry["injection_success_rate"] = sum(c in answer for c, answer in canaries) / len(canaries)
+        report[family] = entry
+    return report
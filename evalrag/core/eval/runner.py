@@ -0,0 +1,449 @@
+# evalrag/core/eval/runner.py
+
+import hashlib
+import json
+import math
+import time
+import uuid
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Tuple
+
+import pandas as pd
+
+from ..executor import AsyncExecutor
+from .metrics import Sample
+from .robustness import robustness_report
+from .scoring import aggregate_scores
+
+ITEM_OK = "ok"
+ITEM_ERROR = "error"
+
+RESULTS_FILE = "results.jsonl"
+SUMMARY_FILE = "summary.json"
+
+
+@dataclass
+class EvalItem:
+    """
+    One row of an evaluation dataset.
+
+    Fields:
+        - `id`: Stable item id (dataset `id` column, else a hash of the question).
+        - `question`: Question sent to the RAG pipeline.
+        - `reference`: Optional reference answer.
+        - `gold_context`: Optional text of the chunk the question was built from.
+        - `gold_doc_ids` / `gold_chunk_ids`: Optional ids of the source document/chunk.
+        - `metadata`: Every other column of the row.
+    """
+    id: str
+    question: str
+    reference: str | None = None
+    gold_context: str | None = None
+    gold_doc_ids: List[str] = field(default_factory=list)
+    gold_chunk_ids: List[str] = field(default_factory=list)
+    metadata: Dict[str, Any] = field(default_factory=dict)
+
+
+def _is_missing(value) -> bool:
+    if isinstance(value, str):
+        return not value.strip()
+    return value is None or (isinstance(value, float) and math.isnan(value))
+
+
+def _as_id_list(value) -> List[str]:
+    """
+    Normalize a gold id field (scalar, list, JSON list or `|`-separated
+    string as found in CSV files) into a list of strings.
+    """
+    if _is_missing(value):
+        return []
+    if isinstance(value, str):
+        text = value.strip()
+        if text.startswith("["):
+            try:
+                return [str(v) for v in json.loads(text)]
+            except ValueError:
+                pass
+        return [v.strip() for v in text.split("|") if v.strip()]
+    if isinstance(value, Iterable):
+        return [str(v) for v in value]
+    return [str(value)]
+
+
+def _first(record: Dict[str, Any], *keys):
+    for key in keys:
+        if not _is_missing(record.get(key)):
+            return record[key]
+    return None
+
+
+def item_from_record(record: Dict[str, Any]) -> EvalItem:
+    """
+    Build an `EvalItem` from a dataset row.
+
+    Recognised columns: `id`, `question`, `reference`/`answer`/`ground_truth`,
+    `context`/`gold_context`, `gold_doc_ids`/`gold_doc_id`/`source_doc` and
+    `gold_chunk_ids`/`gold_chunk_id`/`chunk_id`. Other columns, and the
+    entries of a `metadata` object column, go to `metadata`. Rows produced
+    by the synthetic QA generator therefore load without any mapping.
+    """
+
+    question = str(record["question"]).strip()
+    item_id = _first(record, "id")
+    if item_id is None:
+        item_id = hashlib.sha1(question.encode("utf-8")).hexdigest()[:16]
+
+    known = {
+        "id", "question", "reference", "answer", "ground_truth", "context", "gold_context",
+        "gold_doc_ids", "gold_doc_id", "source_doc", "gold_chunk_ids", "gold_chunk_id", "chunk_id",
+        "metadata",
+    }
+    metadata = dict(record["metadata"]) if isinstance(record.get("metadata"), dict) else {}
+    metadata.update({k: v for k, v in record.items() if k not in known and not _is_missing(v)})
+    return EvalItem(
+        id=str(item_id),
+        question=question,
+        reference=_first(record, "reference", "answer", "ground_truth"),
+        gold_context=_first(record, "gold_context", "context"),
+        gold_doc_ids=_as_id_list(_first(record, "gold_doc_ids", "gold_doc_id", "source_doc")),
+        gold_chunk_ids=_as_id_list(_first(record, "gold_chunk_ids", "gold_chunk_id", "chunk_id")),
+        metadata=metadata,
+    )
+
+
+def read_records(path: str) -> List[Dict[str, Any]]:
+    """
+    Read the rows of a JSONL, JSON, CSV or Parquet dataset file.
+
+    Args:
+        path: Dataset file path; the format is chosen from the extension.
+    """
+
+    path = Path(path)
+    ext = path.suffix.lower()
+    if ext == ".jsonl":
+        with open(path, "r", encoding="utf-8") as f:
+            return [json.loads(line) for line in f if line.strip()]
+    if ext == ".json":
+        with open(path, "r", encoding="utf-8") as f:
+            return json.load(f)
+    if ext == ".csv":
+        return pd.read_csv(path).to_dict(orient="records")
+    if ext == ".parquet":
+        return pd.read_parquet(path).to_dict(orient="records")
+    raise ValueError(f"Unsupported dataset format: {path.name}")
+
+
+def resolve_dataset(
+        dataset: str,
+        split: str | None = None,
+        registry=None
+        ) -> Tuple[List[EvalItem], Dict[str, Any]]:
+    """
+    Load a dataset file or a registered `name@version` dataset.
+
+    Args:
+        dataset: Path of an existing dataset file, else a dataset registry
+            reference (`name@version`, or `name` for the latest version).
+        split: Optional split to keep (registry split definitions, or the
+            `split` column of a file).
+        registry: Optional `DatasetRegistry`; the default registry under
+            `DATA_DIR/datasets` when omitted.
+
+    Returns:
+        The items and a description of the dataset: `dataset` (file path or
+        resolved `name@version`), `content_hash` (registry datasets only)
+        and `split`.
+    """
+
+    if Path(dataset).is_file():
+        records = read_records(dataset)
+        if split is not None:
+            records = [r for r in records if r.get("split") == split]
+        info = {"dataset": str(dataset), "content_hash": None, "split": split}
+    else:
+        from ...datasets.registry import DatasetRegistry
+
+        registry = registry or DatasetRegistry()
+        version = registry.resolve(dataset)
+        records = registry.load(version.ref, split)
+        info = {"dataset": version.ref, "content_hash": version.content_hash, "split": split}
+    return [item_from_record(r) for r in records], info
+
+
+def load_dataset(path: str, split: str | None = None) -> List[EvalItem]:
+    """
+    Load an evaluation dataset from a file or the dataset registry.
+
+    Args:
+        path: Dataset file path (JSONL, JSON, CSV or Parquet) or a registry
+            reference `name@version`; see `resolve_dataset`.
+        split: Optional split to keep.
+
+    Returns:
+        The dataset rows as `EvalItem` objects.
+    """
+    return resolve_dataset(path, split)[0]
+
+
+def read_results(run_dir: str) -> Dict[str, Dict[str, Any]]:
+    """
+    Read the per-item results of a run, keeping the latest record per id.
+
+    A truncated last line (from a crash mid-write) is ignored.
+    """
+
+    results: Dict[str, Dict[str, Any]] = {}
+    path = Path(run_dir) / RESULTS_FILE
+    if not path.exists():
+        return results
+    with open(path, "r", encoding="utf-8") as f:
+        for line in f:
+            try:
+                record = json.loads(line)
+            except ValueError:
+                continue
+            results[record["id"]] = record
+    return results
+
+
+def _mean(values: List[float]) -> float | None:
+    return sum(values) / len(values) if values else None
+
+
+def aggregate_results(records: Iterable[Dict[str, Any]]) -> Dict[str, Any]:
+    """
+    Compute run-level aggregates from per-item result records.
+
+    Metric means cover each metric's `score` and every numeric entry of
+    its `details` (flattened as `<metric>.<key>`, e.g. `ir.hit_rate@5`).
+    Items that failed are counted but excluded from the means.
+
+    Returns:
+        A dict with item counts, the judge aggregate (`judge`, see
+        `scoring.aggregate_scores`), `metrics` means and mean latency.
+    """
+
+    records = list(records)
+    ok = [r for r in records if r["status"] == ITEM_OK]
+
+    metric_values: Dict[str, List[float]] = {}
+    for record in ok:
+        for name, result in record.get("metrics", {}).items():
+            if result["score"] is not None:
+                metric_values.setdefault(name, []).append(result["score"])
+            for key, value in result.get("details", {}).items():
+                if isinstance(value, (int, float)) and not isinstance(value, bool):
+                    metric_values.setdefault(f"{name}.{key}", []).append(value)
+
+    latencies = [r["meta"]["latency_ms"] for r in ok if r.get("meta", {}).get("latency_ms") is not None]
+    return {
+        "n_items": len(records),
+        "n_ok": len(ok),
+        "n_errors": len(records) - len(ok),
+        "judge": aggregate_scores(r["judge"] for r in ok if r.get("judge")),
+        "metrics": {name: _mean(values) for name, values in sorted(metric_values.items())},
+        "latency_ms_mean": _mean(latencies),
+    }
+
+
+def aggregate_by(records: Iterable[Dict[str, Any]], key: str) -> Dict[str, Dict[str, Any]]:
+    """
+    Aggregate result records per value of metadata field `key`
+    (e.g. `question_type`), with `aggregate_results` for each slice.
+
+    Records without the field are left out.
+    """
+    slices: Dict[str, List[Dict[str, Any]]] = {}
+    for record in records:
+        value = (record.get("metadata") or {}).get(key)
+        if value is not None:
+            slices.setdefault(str(value), []).append(record)
+    return {value: aggregate_results(subset) for value, subset in sorted(slices.items())}
+
+
+class EvaluationRunner:
+    """
+    Batch evaluation of a RAG pipeline over a dataset.
+
+    Responsibilities:
+      - Load a JSONL/CSV/Parquet dataset file, or a `name@version` dataset
+        from the `DatasetRegistry`, of questions and references.
+      - Answer each question with `RAG.generate_answer`.
+      - Score each answer with the `Evaluator` judge and configured metrics.
+      - Append per-item results to `results.jsonl` as they complete and
+        write run aggregates to `summary.json`.
+      - Resume an interrupted run by skipping item ids already scored.
+      - Process up to `concurrency` items at once; wrap the RAG and judge
+        clients with `executor.guard_client` to rate-limit and retry the
+        underlying LLM calls.
+      - Optionally persist run metadata, items and scores to a `RunStore`.
+
+    Typical usage:
+      1. `runner = EvaluationRunner(rag, evaluator, runs_dir)`
+      2. `summary = runner.run("testset@1.2.0", run_id="baseline")`
+      3. Re-run with the same `run_id` after a crash to continue.
+
+    Args:
+        rag: `RAG` instance (or any object with `generate_answer(question)`).
+        evaluator: `Evaluator` instance.
+        runs_dir: Directory holding one sub-directory per run.
+        concurrency: Number of items evaluated in parallel.
+        store: Optional `RunStore` receiving run metadata and results.
+        registry: Optional `DatasetRegistry` resolving `name@version`
+            datasets; the default registry when omitted.
+    """
+
+    def __init__(self, rag, evaluator, runs_dir: str, concurrency: int = 1, store=None, registry=None) -> None:
+        self.rag = rag
+        self.evaluator = evaluator
+        self.runs_dir = Path(runs_dir)
+        self.executor = AsyncExecutor(concurrency=concurrency)
+        self.store = store
+        self.registry = registry
+
+    def evaluate_item(self, item: EvalItem) -> Dict[str, Any]:
+        """
+        Answer and score a single item.
+
+        Returns:
+            The result record written to `results.jsonl`. Exceptions are
+            caught and recorded with status "error" so the item is retried
+            on resume.
+        """
+
+        record: Dict[str, Any] = {
+            "id": item.id,
+            "question": item.question,
+            "reference": item.reference,
+            "metadata": item.metadata,
+        }
+        try:
+            # prompt-injection items of robustness datasets poison the retrieved documents
+            injection = {"injected_text": item.metadata["injected_text"]} if item.metadata.get("injected_text") else {}
+            generated = self.rag.generate_answer(question=item.question, **injection)
+            sample = Sample(
+                question=item.question,
+                answer=generated["answer"],
+                contexts=generated["contexts"],
+                reference=item.reference,
+                gold_doc_ids=item.gold_doc_ids,
+                gold_chunk_ids=item.gold_chunk_ids,
+                metadata=item.metadata,
+            )
+            record.update(
+                answer=generated["answer"],
+                contexts=generated["contexts"],
+                meta=generated.get("meta", {}),
+                judge=self.evaluator.evaluate_answer(item.question, sample.answer, sample.contexts),
+                metrics={name: asdict(r) for name, r in self.evaluator.score_sample(sample).items()},
+                status=ITEM_OK,
+            )
+        except Exception as e:
+            record.update(status=ITEM_ERROR, error=f"{type(e).__name__}: {e}")
+        return record
+
+    def run(
+            self,
+            dataset_path: str,
+            run_id: str | None = None,
+            limit: int | None = None,
+            split: str | None = None
+            ) -> Dict[str, Any]:
+        """
+        Evaluate every item of `dataset_path` and write the run outputs.
+
+        Args:
+            dataset_path: Dataset file, or registered dataset as
+                `name@version`, to evaluate. Registry references are stored
+                resolved (a bare `name` becomes `name@<latest>`) together
+                with the content hash.
+            run_id: Run identifier; reuse an existing one to resume it.
+            limit: Optional cap on the number of dataset items.
+            split: Optional dataset split to evaluate.
+
+        Returns:
+            The run summary, also written to `summary.json`.
+        """
+
+        run_id = run_id or time.strftime("%Y%m%d-%H%M%S-") + uuid.uuid4().hex[:6]
+        run_dir = self.runs_dir / run_id
+        run_dir.mkdir(parents=True, exist_ok=True)
+        items, dataset = resolve_dataset(dataset_path, split, self.registry)
+        items = items[:limit]
+
+        if self.store is not None:
+            self.store.start_run(
+                run_id=run_id,
+                dataset=dataset["dataset"],
+                config={
+                    "dataset": dataset,
+                    "eval": asdict(self.evaluator.config),
+                    "rag": {"top_k": getattr(self.rag, "top_k", None)},
+                },
+                prompt_config=self.evaluator.prompt_config,
+                judge_model=getattr(self.evaluator.judge_client, "model_name", None),
+                rag_model=getattr(getattr(self.rag, "llm_client", None), "model_name", None),
+            )
+
+        done = {i for i, r in read_results(run_dir).items() if r["status"] == ITEM_OK}
+        pending = [item for item in items if item.id not in done]
+
+        results_path = run_dir / RESULTS_FILE
+        if results_path.exists() and results_path.stat().st_size:
+            # terminate a line left truncated by a crash before appending
+            with open(results_path, "rb") as f:
+                f.seek(-1, 2)
+                truncated = f.read(1) != b"\n"
+            if truncated:
+                with open(results_path, "a", encoding="utf-8") as f:
+                    f.write("\n")
+
+        with open(results_path, "a", encoding="utf-8") as f:
+            def write(record):
+                f.write(json.dumps(record, default=str) + "\n")
+                f.flush()
+                if self.store is not None:
+                    self.store.save_item(run_id, record)
+
+            self.executor.run(self.evaluate_item, pending, on_result=write)
+
+        return self.write_summary(run_id, dataset, [i.id for i in items])
+
+    def write_summary(self, run_id: str, dataset: Dict[str, Any], item_ids: List[str]) -> Dict[str, Any]:
+        """
+        Aggregate the results of `run_id` and write `summary.json`.
+
+        Args:
+            run_id: Run identifier.
+            dataset: Dataset description from `resolve_dataset`.
+            item_ids: Ids of the dataset items in the run.
+
+        Datasets with a `question_type` column also get per-type aggregates
+        under `by_question_type`; robustness datasets (`perturbation`
+        column) get per-family aggregates under `by_perturbation` and the
+        drop against the clean items under `robustness`.
+        """
+        run_dir = self.runs_dir / run_id
+        results = read_results(run_dir)
+        records = [results[i] for i in item_ids if i in results]
+        summary = {
+            "run_id": run_id,
+            "dataset": dataset["dataset"],
+            "dataset_hash": dataset["content_hash"],
+            "split": dataset["split"],
+            **aggregate_results(records),
+        }
+        by_type = aggregate_by(records, "question_type")
+        if by_type:
+            summary["by_question_type"] = by_type
+        by_perturbation = aggregate_by(records, "perturbation")
+        if by_perturbation:
+            summary["by_perturbation"] = by_perturbation
+            summary["robustness"] = robustness_report(records, by_perturbation)
+        with open(run_dir / SUMMARY_FILE, "w", encoding="utf-8") as f:
+            json.dump(summary, f, indent=2)
+        if self.store is not None:
+            self.store.finish_run(run_id, summary)
+        return summary
@@ -0,0 +1,128 @@
+# evalrag/core/eval/scoring.py
+
+"""
+Composite scoring shared by every consumer of judge results.
+
+All per-answer and per-run aggregate numbers are computed here so the
+release gate, the runner and the dashboard agree on a single definition.
+"""
+
+import math
+import warnings
+from typing import Any, Dict, Iterable
+
+# criterion name -> `EvalConfig` field holding its weight
+WEIGHT_FIELDS = {
+    "correctness": "correctness_weight",
+    "faithfulness": "faithfulness_weight",
+    "relevance": "context_relevance_weight",
+}
+
+SCORE_MIN = 1
+SCORE_MAX = 5
+
+
+def resolve_weights(config) -> Dict[str, float]:
+    """
+    Read and validate the criterion weights from an `EvalConfig`.
+
+    Weights must be non-negative with a positive sum. When they do not sum
+    to 1 they are renormalized and a `UserWarning` is emitted.
+
+    Args:
+        config: `EvalConfig` (or any object with the weight attributes).
+
+    Returns:
+        Criterion name to weight, summing to 1.
+    """
+
+    weights = {name: float(getattr(config, attr)) for name, attr in WEIGHT_FIELDS.items()}
+
+    if any(w < 0 for w in weights.values()):
+        raise ValueError(f"Evaluation weights must be non-negative, got {weights}")
+
+    total = sum(weights.values())
+    if total <= 0:
+        raise ValueError("Evaluation weights must sum to a positive value")
+
+    if not math.isclose(total, 1.0, abs_tol=1e-6):
+        warnings.warn(
+            f"Evaluation weights sum to {total:.4f}, not 1; renormalizing {weights}",
+            UserWarning,
+            stacklevel=2,
+        )
+        weights = {name: w / total for name, w in weights.items()}
+
+    return weights
+
+
+def normalize_score(score: float) -> float:
+    """
+    Map a 1-5 judge score onto [0, 1].
+    """
+    return (float(score) - SCORE_MIN) / (SCORE_MAX - SCORE_MIN)
+
+
+def composite_score(scores: Dict[str, float], weights: Dict[str, float]) -> float | None:
+    """
+    Weighted composite of normalized criterion scores.
+
+    Args:
+        scores: Criterion name to 1-5 score.
+        weights: Criterion name to weight, as returned by `resolve_weights`.
+
+    Returns:
+        A value in [0, 1], or `None` if a weighted criterion is missing.
+    """
+
+    if any(name not in scores for name, w in weights.items() if w > 0):
+        return None
+    return sum(w * normalize_score(scores[name]) for name, w in weights.items() if w > 0)
+
+
+def is_hallucination(scores: Dict[str, float], threshold: float) -> bool | None:
+    """
+    Flag an answer whose faithfulness score falls below `threshold`.
+
+    Returns:
+        `True`/`False`, or `None` when no faithfulness score is available.
+    """
+
+    if "faithfulness" not in scores:
+        return None
+    return scores["faithfulness"] < threshold
+
+
+def aggregate_scores(results: Iterable[Dict[str, Any]]) -> Dict[str, Any]:
+    """
+    Aggregate per-answer evaluation results into run-level numbers.
+
+    Answers without a composite (e.g. judge parse errors) are excluded from
+    the means and counted separately.
+
+    Args:
+        results: Dicts as returned by `Evaluator.evaluate_answer`.
+
+    Returns:
+        A dict with `n`, `n_scored`, `parse_errors`, `composite_mean`,
+        `hallucination_rate` and per-criterion means under `criteria`.
+    """
+
+    results = list(results)
+    scored = [r for r in results if r.get("composite") is not None]
+    flagged = [r["hallucination"] for r in results if r.get("hallucination") is not None]
+
+    criteria: Dict[str, float] = {}
+    for name in WEIGHT_FIELDS:
+        values = [r["scores"][name] for r in results if name in r.get("scores", {})]
+        if values:
+            criteria[name] = sum(values) / len(values)
+
+    return {
+        "n": len(results),
+        "n_scored": len(scored),
+        "parse_errors": len(results) - len(scored),
+        "composite_mean": sum(r["composite"] for r in scored) / len(scored) if scored else None,
+        "hallucination_rate": sum(flagged) / len(flagged) if flagged else None,
+        "criteria": criteria,
+    }
@@ -0,0 +1,25 @@
+# evalrag/core/eval/similarity.py
+
+import math
+from typing import List, Sequence
+
+
+def embed_texts(embedder, texts: List[str]) -> List[List[float]]:
+    """
+    Embed `texts` with either a LangChain embeddings model
+    (`embed_documents`) or a client exposing `embed(text)`.
+    """
+    if embedder is None:
+        raise ValueError("This metric needs an embedder; pass one to the Evaluator")
+    if hasattr(embedder, "embed_documents"):
+        return embedder.embed_documents(texts)
+    return [embedder.embed(text) for text in texts]
+
+
+def cosine_similarity(a: Sequence[float], b: Sequence[float]) -> float:
+    """
+    Cosine similarity of two vectors; 0 when either has zero norm.
+    """
+    dot = sum(x * y for x, y in zip(a, b))
+    norm = math.sqrt(sum(x * x for x in a)) * math.sqrt(sum(y * y for y in b))
+    return dot / norm if norm else 0.0
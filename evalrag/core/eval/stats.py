@@ -0,0 +1,148 @@
+# evalrag/core/eval/stats.py
+
+"""
+Paired significance tests for comparing two evaluation runs.
+
+Both runs score the same items, so every test works on per-item score
+differences (candidate - base) rather than on two independent samples.
+"""
+
+from statistics import NormalDist
+from typing import Any, Dict, Sequence, Tuple
+
+import numpy as np
+from scipy import stats as scipy_stats
+
+_BATCH = 1000
+
+
+def _diffs(base: Sequence[float], candidate: Sequence[float]) -> np.ndarray:
+    if len(base) != len(candidate):
+        raise ValueError("Paired tests need the same number of base and candidate scores")
+    return np.asarray(candidate, dtype=float) - np.asarray(base, dtype=float)
+
+
+def paired_bootstrap_ci(
+        base: Sequence[float],
+        candidate: Sequence[float],
+        n_resamples: int = 10000,
+        confidence: float = 0.95,
+        seed: int | None = 0
+        ) -> Tuple[float, float]:
+    """
+    Percentile bootstrap confidence interval of the mean paired difference.
+
+    Items are resampled with replacement, keeping base and candidate scores
+    of an item together.
+
+    Returns:
+        `(low, high)` bounds of the interval for `mean(candidate - base)`.
+    """
+    diffs = _diffs(base, candidate)
+    if len(diffs) == 0:
+        return (float("nan"), float("nan"))
+
+    rng = np.random.default_rng(seed)
+    means = []
+    for start in range(0, n_resamples, _BATCH):
+        size = min(_BATCH, n_resamples - start)
+        idx = rng.integers(0, len(diffs), size=(size, len(diffs)))
+        means.append(diffs[idx].mean(axis=1))
+    means = np.concatenate(means)
+
+    tail = (1 - confidence) / 2 * 100
+    low, high = np.percentile(means, [tail, 100 - tail])
+    return (float(low), float(high))
+
+
+def paired_permutation_test(
+        base: Sequence[float],
+        candidate: Sequence[float],
+        n_resamples: int = 10000,
+        seed: int | None = 0
+        ) -> float:
+    """
+    Two-sided sign-flip permutation test of the mean paired difference.
+
+    Under the null hypothesis the sign of each item's difference is
+    exchangeable; the p-value is the share of random sign assignments whose
+    mean difference is at least as extreme as the observed one.
+    """
+    diffs = _diffs(base, candidate)
+    if len(diffs) == 0 or not np.any(diffs):
+        return 1.0
+
+    rng = np.random.default_rng(seed)
+    observed = abs(diffs.mean())
+    extreme = 0
+    for start in range(0, n_resamples, _BATCH):
+        size = min(_BATCH, n_resamples - start)
+        signs = rng.choice([-1.0, 1.0], size=(size, len(diffs)))
+        extreme += int(np.sum(np.abs((signs * diffs).mean(axis=1)) >= observed - 1e-12))
+    return (extreme + 1) / (n_resamples + 1)
+
+
+def wilcoxon_test(base: Sequence[float], candidate: Sequence[float]) -> float:
+    """
+    Two-sided Wilcoxon signed-rank test p-value.
+
+    Zero differences are discarded (Wilcoxon's method); when every
+    difference is zero the runs are identical and the p-value is 1.
+    """
+    diffs = _diffs(base, candidate)
+    if not np.any(diffs):
+        return 1.0
+    return float(scipy_stats.wilcoxon(diffs, zero_method="wilcox").pvalue)
+
+
+def minimum_detectable_effect(
+        base: Sequence[float],
+        candidate: Sequence[float],
+        alpha: float = 0.05,
+        power: float = 0.8
+        ) -> float:
+    """
+    Smallest mean paired difference detectable at `alpha` with `power`.
+
+    Uses the normal approximation `(z_{1-alpha/2} + z_{power}) * sd / sqrt(n)`
+    with the standard deviation of the observed differences. Differences
+    smaller than this are not reliably distinguishable from noise at the
+    current dataset size.
+    """
+    diffs = _diffs(base, candidate)
+    if len(diffs) < 2:
+        return float("inf")
+    z = NormalDist().inv_cdf(1 - alpha / 2) + NormalDist().inv_cdf(power)
+    return float(z * diffs.std(ddof=1) / np.sqrt(len(diffs)))
+
+
+def significance_report(
+        base: Sequence[float],
+        candidate: Sequence[float],
+        alpha: float = 0.05,
+        n_resamples: int = 10000,
+        seed: int | None = 0
+        ) -> Dict[str, Any]:
+    """
+    Run every paired test on one metric.
+
+    Returns:
+        A dict with `n`, `mean_delta`, `ci_low`/`ci_high` (bootstrap, at
+        `1 - alpha` confidence), `permutation_p`, `wilcoxon_p`,
+        `min_detectable_effect` and `significant` (both p-values below
+        `alpha`).
+    """
+    diffs = _diffs(base, candidate)
+    ci_low, ci_high = paired_bootstrap_ci(base, candidate, n_resamples, 1 - alpha, seed)
+    permutation_p = paired_permutation_test(base, candidate, n_resamples, seed)
+    wilcoxon_p = wilcoxon_test(base, candidate) if len(diffs) else 1.0
+    return {
+        "n": int(len(diffs)),
+        "mean_delta": float(diffs.mean()) if len(diffs) else float("nan"),
+        "ci_low": ci_low,
+        "ci_high": ci_high,
+        "permutation_p": permutation_p,
+        "wilcoxon_p": wilcoxon_p,
+        "min_detectable_effect": minimum_detectable_effect(base, candidate, alpha),
+        "significant": permutation_p < alpha and wilcoxon_p < alpha,
+    }
@@ -0,0 +1,295 @@
+# evalrag/core/executor.py
+
+import asyncio
+import threading
+import time
+from concurrent.futures import ThreadPoolExecutor, TimeoutError as FutureTimeoutError
+from typing import Any, Callable, Dict, Iterable, List
+
+from tenacity import (
+    Retrying,
+    retry_if_exception,
+    stop_after_attempt,
+    wait_exponential_jitter,
+)
+
+
+class RateLimitError(Exception):
+    """
+    Raised when a provider rejects a call for exceeding its rate limit (HTTP 429).
+
+    Args:
+        message: Error message.
+        retry_after: Seconds the provider asked us to wait, if known.
+    """
+
+    def __init__(self, message: str = "rate limited", retry_after: float | None = None) -> None:
+        super().__init__(message)
+        self.retry_after = retry_after
+        self.status_code = 429
+
+
+def _status_code(exc: BaseException) -> int | None:
+    code = getattr(exc, "status_code", None)
+    if code is None:
+        code = getattr(getattr(exc, "response", None), "status_code", None)
+    return code if isinstance(code, int) else None
+
+
+def is_retryable(exc: BaseException) -> bool:
+    """
+    Decide whether a failed provider call should be retried.
+
+    Retries rate limits (429), server errors (5xx), timeouts and connection
+    errors, recognising provider SDK exceptions by their `status_code`.
+    """
+    if isinstance(exc, (RateLimitError, TimeoutError, ConnectionError)):
+        return True
+    code = _status_code(exc)
+    return code is not None and (code == 429 or code >= 500)
+
+
+class RateLimiter:
+    """
+    Thread-safe token bucket enforcing request and token rate limits.
+
+    Both budgets refill continuously; `acquire` blocks until the call fits.
+    After a 429, `pause` holds every caller back for the provider's
+    `retry_after` window.
+
+    Args:
+        requests_per_minute: Maximum requests per minute, `None` for unlimited.
+        tokens_per_minute: Maximum (estimated) tokens per minute, `None` for unlimited.
+    """
+
+    def __init__(
+            self,
+            requests_per_minute: float | None = None,
+            tokens_per_minute: float | None = None
+            ) -> None:
+        self.requests_per_minute = requests_per_minute
+        self.tokens_per_minute = tokens_per_minute
+        self._requests = float(requests_per_minute or 0)
+        self._tokens = float(tokens_per_minute or 0)
+        self._updated = time.monotonic()
+        self._paused_until = 0.0
+        self._lock = threading.Lock()
+
+    def _refill(self, now: float) -> None:
+        elapsed = now - self._updated
+        self._updated = now
+        if self.requests_per_minute:
+            self._requests = min(
+                self.requests_per_minute, self._requests + elapsed * self.requests_per_minute / 60
+            )
+        if self.tokens_per_minute:
+            self._tokens = min(
+                self.tokens_per_minute, self._tokens + elapsed * self.tokens_per_minute / 60
+            )
+
+    def acquire(self, tokens: int = 0) -> None:
+        """
+        Block until one request of `tokens` estimated tokens is allowed.
+        """
+        while True:
+            with self._lock:
+                now = time.monotonic()
+                self._refill(now)
+                wait = max(0.0, self._paused_until - now)
+                if self.requests_per_minute and self._requests < 1:
+                    wait = max(wait, (1 - self._requests) * 60 / self.requests_per_minute)
+                if self.tokens_per_minute and tokens:
+                    needed = min(tokens, self.tokens_per_minute)
+                    if self._tokens < needed:
+                        wait = max(wait, (needed - self._tokens) * 60 / self.tokens_per_minute)
+                if wait <= 0:
+                    if self.requests_per_minute:
+                        self._requests -= 1
+                    if self.tokens_per_minute:
+                        self._tokens -= tokens
+                    return
+            time.sleep(wait)
+
+    def pause(self, seconds: float) -> None:
+        """
+        Hold back all callers for `seconds`.
+        """
+        with self._lock:
+            self._paused_until = max(self._paused_until, time.monotonic() + seconds)
+
+
+_RATE_LIMITERS: Dict[str, RateLimiter] = {}
+_RATE_LIMITERS_LOCK = threading.Lock()
+
+
+def get_rate_limiter(provider: str, rate_limits: Dict[str, Dict[str, Any]] | None = None) -> RateLimiter:
+    """
+    Return the process-wide `RateLimiter` of `provider`.
+
+    Every client of the same provider shares one limiter, so the RAG
+    generator, the judge and dataset generation draw from one budget.
+
+    Args:
+        provider: Provider name (e.g. "HF", "OPENAI").
+        rate_limits: Provider name to `requests_per_minute` /
+            `tokens_per_minute`, used when the limiter is first created.
+    """
+    with _RATE_LIMITERS_LOCK:
+        if provider not in _RATE_LIMITERS:
+            limits = (rate_limits or {}).get(provider, {})
+            _RATE_LIMITERS[provider] = RateLimiter(
+                requests_per_minute=limits.get("requests_per_minute"),
+                tokens_per_minute=limits.get("tokens_per_minute"),
+            )
+        return _RATE_LIMITERS[provider]
+
+
+class GuardedClient:
+    """
+    Wrap an LLM client with rate limiting, retries and per-call timeouts.
+
+    Exposes the same `generate(prompt, **kwargs)` interface as the wrapped
+    client so it can be handed to `RAG`, the `Evaluator` or the dataset
+    generator unchanged. Retries use exponential backoff with jitter
+    (tenacity) for the errors accepted by `is_retryable`.
+
+    Calls run on a pool of `max_workers` threads so they can time out. A
+    timed-out call is abandoned rather than cancelled: the provider SDK
+    keeps running it, holding its worker, until its own timeout fires. A
+    slow provider can therefore leave every worker busy; later calls then
+    wait in the pool queue, time out in turn and are retried, so the pool
+    stays bounded instead of growing with each leaked call.
+
+    Args:
+        client: Client exposing `generate(prompt, **kwargs)`.
+        limiter: `RateLimiter` shared by the provider.
+        max_retries: Number of retries after the first attempt.
+        timeout_s: Per-attempt timeout in seconds, `None` to disable.
+        backoff_min_s: Initial backoff delay.
+        backoff_max_s: Maximum backoff delay.
+        max_workers: Calls running at once; the execution concurrency,
+            since each in-flight item makes at most one call at a time.
+    """
+
+    def __init__(
+            self,
+            client,
+            limiter: RateLimiter | None = None,
+            max_retries: int = 5,
+            timeout_s: float | None = 120,
+            backoff_min_s: float = 1,
+            backoff_max_s: float = 60,
+            max_workers: int = 4
+            ) -> None:
+        self.client = client
+        self.limiter = limiter or RateLimiter()
+        self.max_retries = max_retries
+        self.timeout_s = timeout_s
+        self.backoff_min_s = backoff_min_s
+        self.backoff_max_s = backoff_max_s
+        self._pool = ThreadPoolExecutor(
+            max_workers=max(1, int(max_workers)), thread_name_prefix="llm-call"
+        ) if timeout_s else None
+
+    @property
+    def model_name(self):
+        return getattr(self.client, "model_name", None)
+
+    def _attempt(self, prompt: str, **kwargs) -> Dict[str, Any]:
+        self.limiter.acquire(tokens=len(prompt) // 4)
+        try:
+            if self._pool is None:
+                return self.client.generate(prompt, **kwargs)
+            future = self._pool.submit(self.client.generate, prompt, **kwargs)
+            try:
+                return future.result(timeout=self.timeout_s)
+            except FutureTimeoutError:
+                raise TimeoutError(f"LLM call exceeded {self.timeout_s}s") from None
+        except RateLimitError as e:
+            if e.retry_after:
+                self.limiter.pause(e.retry_after)
+            raise
+
+    def generate(self, prompt: str, **kwargs) -> Dict[str, Any]:
+        """
+        Call the wrapped client, retrying retryable failures.
+        """
+        retrying = Retrying(
+            stop=stop_after_attempt(self.max_retries + 1),
+            wait=wait_exponential_jitter(initial=self.backoff_min_s, max=self.backoff_max_s),
+            retry=retry_if_exception(is_retryable),
+            reraise=True,
+        )
+        return retrying(self._attempt, prompt, **kwargs)
+
+
+def guard_client(client, provider: str, execution) -> GuardedClient:
+    """
+    Wrap `client` according to an `ExecutionConfig`.
+
+    Args:
+        client: Client exposing `generate(prompt, **kwargs)`.
+        provider: Provider name used to pick the shared rate limiter.
+        execution: `ExecutionConfig` with retry, timeout and rate-limit settings.
+    """
+    return GuardedClient(
+        client,
+        limiter=get_rate_limiter(provider, execution.rate_limits),
+        max_retries=execution.max_retries,
+        timeout_s=execution.timeout_s,
+        backoff_min_s=execution.backoff_min_s,
+        backoff_max_s=execution.backoff_max_s,
+        max_workers=execution.concurrency,
+    )
+
+
+class AsyncExecutor:
+    """
+    Run blocking work items concurrently with bounded parallelism.
+
+    Each item is processed by `fn` in a worker thread; at most
+    `concurrency` items are in flight. Results are reported through
+    `on_result` as they complete (on the event loop thread, so callbacks
+    need no locking) and returned in input order.
+
+    Typical usage:
+      1. `executor = AsyncExecutor(concurrency=8)`
+      2. `results = executor.run(fn, items, on_result=write_line)`
+
+    Args:
+        concurrency: Maximum number of items processed at once.
+    """
+
+    def __init__(self, concurrency: int = 4) -> None:
+        self.concurrency = max(1, int(concurrency))
+
+    async def map(
+            self,
+            fn: Callable[[Any], Any],
+            items: Iterable[Any],
+            on_result: Callable[[Any], None] | None = None
+            ) -> List[Any]:
+        """
+        Apply `fn` to every item concurrently.
+        """
+        semaphore = asyncio.Semaphore(self.concurrency)
+
+        async def worker(item):
+            async with semaphore:
+                result = await asyncio.to_thread(fn, item)
+            if on_result is not None:
+                on_result(result)
+            return result
+
+        return await asyncio.gather(*(worker(item) for item in items))
+
+    def run(
+            self,
+            fn: Callable[[Any], Any],
+            items: Iterable[Any],
+            on_result: Callable[[Any], None] | None = None
+            ) -> List[Any]:
+        """
+        Synchronous entry point around `map`.
+        """
+        return asyncio.run(self.map(fn, items, on_result=on_result))
@@ -1,11 +1,14 @@
 # evalrag/core/ingestion.py
+import hashlib
+import json
 import os
+import time
+from dataclasses import dataclass, field
+from pathlib import Path
 
-from typing import List
+from typing import Any, Dict, List
 
 from langchain_core.documents import Document
-from langchain_community.document_loaders import PyPDFLoader
-from langchain_text_splitters import RecursiveCharacterTextSplitter
 from langchain_huggingface import HuggingFaceEmbeddings
 from langchain_openai import OpenAIEmbeddings
 
@@ -13,6 +16,47 @@ import faiss
 from langchain_community.docstore.in_memory import InMemoryDocstore
 from langchain_community.vectorstores import FAISS
 
+from .chunking import CHUNKER_REGISTRY, get_chunker
+from .loaders import load_file, supported_extensions
+
+INDEX_STATE_FILE = "indexed_docs.json"
+
+
+def file_sha256(path: str) -> str:
+    """
+    SHA-256 of a file's bytes, read in 1 MiB blocks.
+    """
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for block in iter(lambda: f.read(1 << 20), b""):
+            digest.update(block)
+    return digest.hexdigest()
+
+
+def normalize_source(source: str) -> str:
+    """
+    Resolved absolute form of a source path, so that relative and absolute
+    spellings of one file give the same `doc_id` and index-state entry.
+    """
+    return str(Path(source).resolve())
+
+
+@dataclass
+class SyncPlan:
+    """
+    Changes needed to bring the index in line with a source directory.
+
+    Fields:
+        - `added`: Files not indexed yet.
+        - `updated`: Indexed files whose content changed.
+        - `deleted`: Indexed sources under the directory that no longer exist.
+        - `unchanged`: Indexed files with the same content.
+    """
+    added: List[str] = field(default_factory=list)
+    updated: List[str] = field(default_factory=list)
+    deleted: List[str] = field(default_factory=list)
+    unchanged: List[str] = field(default_factory=list)
+
 
 class Ingestion:
 
@@ -20,16 +64,26 @@ class Ingestion:
     Ingestion pipeline helper for building a vector index from files.
 
     Responsibilities:
-      - Discover and load supported files from a directory (currently PDFs).
+      - Discover and load supported files from a directory (see
+        `loaders.LOADER_REGISTRY`).
       - Split documents into chunks suitable for embedding.
       - Provide an embeddings-model factory for supported providers.
-      - Build and persist a FAISS-based vector store.
+      - Build a FAISS-based vector store, or extend the persisted one:
+        chunks are stored under their `chunk_id`, and re-ingesting a
+        document first deletes its previous chunks.
+      - Track indexed documents in a manifest (`indexed_docs.json` next to
+        the index: source path, size, mtime, content hash, chunk ids), so
+        unchanged files are skipped.
+      - Sync a directory tree with the index: embed added and changed
+        files only and purge deleted ones.
 
     Typical usage:
       1. `loader(path)` to load source documents.
-      2. `splitter(...)` to chunk them for embedding.
+      2. `splitter(...)` to chunk them for embedding (see
+         `chunking.CHUNKER_REGISTRY`).
       3. `get_embedding_model(provider)` to obtain embeddings.
-      4. `vector_store(embeddings, splits, path)` to create and save the index.
+      4. `vector_store(embeddings, splits, path)` to add them to the index.
+      Or `indexing(filename)` for all four steps.
 
     Args:
         config: Configuration object or mapping used by ingestion routines.
@@ -50,58 +104,89 @@ class Ingestion:
 
     def loader(self, filename: str)->List[Document]:
         """
-        Load documents from the given filesystem `path`.
+        Load the file at `filename` into LangChain `Document` objects.
 
-        Currently this scans the directory at `path` and loads files it
-        recognizes (PDFs) into LangChain `Document` objects using
-        `PyPDFLoader`.
+        The loader is picked from `loaders.LOADER_REGISTRY` by file
+        extension (PDF, Markdown, HTML, DOCX, text, CSV, JSONL and any
+        format added with `loaders.register_loader`); structure such as
+        pages, heading paths and table rows is kept in metadata.
 
         Args:
-            filename: Directory path containing files to load.
+            filename: Path of the file to load.
 
         Returns:
-            A list where each element is a list of `Document` objects
-            corresponding to a single source file.
+            The `Document` objects of the file.
         """
 
-        pdf_loader = PyPDFLoader(filename)
-        docs = pdf_loader.load()
-        
-        return docs
+        return load_file(filename)
 
     def splitter(
             self, 
             docs: List[Document], 
             chunk_size: int = 100, 
-            chunk_overlap: int = 10
+            chunk_overlap: int = 10,
+            chunker: str | None = None,
+            embeddings=None
             )->List[Document]:
 
         """
         Split loaded documents into smaller chunks for embedding/indexing.
 
-        This method accepts a list of document-lists (one inner list per
-        source file) and applies `RecursiveCharacterTextSplitter` to each
-        to produce chunked `Document` objects suitable for embedding.
+        The chunking strategy is taken from `chunking.CHUNKER_REGISTRY`:
+        `chunker` when given, else `ingestion.chunker` from the config
+        (`recursive` by default), with its `ingestion.chunker_params`.
 
         Args:
-            docs: List of document lists to split.
-            chunk_size: Maximum characters per chunk.
+            docs: Documents to split.
+            chunk_size: Maximum chunk size (characters; tokens for the
+                `token` chunker).
             chunk_overlap: Overlap between adjacent chunks.
+            chunker: Optional registered chunker name overriding the config.
+            embeddings: Embeddings model for chunkers that need one
+                (`semantic`); the configured provider's model when omitted.
 
         Returns:
-            A list where each element is the list of chunked `Document`
-            objects derived from the corresponding input document list.
+            The chunked `Document` objects. Every chunk carries a `doc_id`
+            (its resolved `source` path), a stable `chunk_id` (`<doc_id>#<n>`, numbered
+            per document) and its `heading_path` in its metadata, which
+            retrieval results and generated datasets reference.
         """
 
-        text_splitter = RecursiveCharacterTextSplitter(
-            chunk_size=chunk_size, 
-            chunk_overlap=chunk_overlap, 
-            add_start_index=True
+        name = chunker or getattr(self.config, "chunker", "recursive")
+        if name in CHUNKER_REGISTRY and CHUNKER_REGISTRY[name].needs_embeddings and embeddings is None:
+            embeddings = self.get_embedding_model(provider=self.config.provider)
+        text_splitter = get_chunker(
+            name,
+            chunk_size=chunk_size,
+            chunk_overlap=chunk_overlap,
+            params=(getattr(self.config, "chunker_params", None) or {}).get(name),
+            embeddings=embeddings,
             )
         
-        return text_splitter.split_documents(docs)
+        return self.assign_chunk_ids(text_splitter.split(docs))
+
+    @staticmethod
+    def assign_chunk_ids(chunks: List[Document]) -> List[Document]:
+        """
+        Set `doc_id` and `chunk_id` metadata on chunks, in document order.
+
+        The `doc_id` defaults to the resolved `source` path (see
+        `normalize_source`). Chunks that already have a `doc_id` or
+        `chunk_id` keep it.
+        """
+        counters: Dict[str, int] = {}
+        for chunk in chunks:
+            if "doc_id" not in chunk.metadata:
+                source = chunk.metadata.get("source")
+                chunk.metadata["doc_id"] = normalize_source(source) if source else "unknown"
+            doc_id = str(chunk.metadata["doc_id"])
+            n = counters.get(doc_id, 0)
+            counters[doc_id] = n + 1
+            chunk.metadata.setdefault("chunk_id", f"{doc_id}#{n}")
+        return chunks
         
-    def get_embedding_model(self, provider: str = "HF"):
+    @staticmethod
+    def get_embedding_model(provider: str = "HF"):
         """
         Return an embeddings model instance for the requested provider.
 
@@ -123,67 +208,282 @@ class Ingestion:
         elif provider == "OPENAI":
             return OpenAIEmbeddings(model="text-embedding-3-large")
 
-    def vector_store(
-            self, 
-            embeddings, 
-            splits: List[Document], 
-            vector_store_path: str):
+    @staticmethod
+    def load_index_state(vector_store_path: str) -> Dict[str, Dict[str, Any]]:
         """
-        Build a FAISS vector store from provided document splits and save it.
-
-        This creates an in-memory FAISS index using the dimensionality
-        inferred from the provided `embeddings` object, wraps it with the
-        LangChain `FAISS` helper, adds the given `splits`, and persists the
-        index to `vector_store_path`.
+        Documents indexed in the store at `vector_store_path`.
 
-        Args:
-            embeddings: Embedding model instance with an `embed_query` method.
-            splits: List of chunked `Document` objects to add to the store.
-            vector_store_path: Filesystem path where the FAISS index will be saved.
+        Returns:
+            `doc_id` to `source`, `size`, `mtime`, `sha256`, `chunk_ids` and
+            `indexed_at`; empty when nothing was indexed yet.
         """
+        path = Path(vector_store_path) / INDEX_STATE_FILE
+        if not path.exists():
+            return {}
+        with open(path, "r", encoding="utf-8") as f:
+            return json.load(f)
+
+    @staticmethod
+    def save_index_state(vector_store_path: str, state: Dict[str, Dict[str, Any]]) -> None:
+        path = Path(vector_store_path) / INDEX_STATE_FILE
+        path.parent.mkdir(parents=True, exist_ok=True)
+        with open(path, "w", encoding="utf-8") as f:
+            json.dump(state, f, indent=2, sort_keys=True)
+
+    @staticmethod
+    def open_vector_store(embeddings, vector_store_path: str) -> FAISS:
+        """
+        Load the FAISS store persisted at `vector_store_path`, or create an
+        empty one when there is none yet.
 
-        embedding_dim = len(embeddings.embed_query("hello world"))
-        index = faiss.IndexFlatL2(embedding_dim)
-        store = FAISS(
+        Only a new store embeds a probe text, to size its index.
+        """
+        if (Path(vector_store_path) / "index.faiss").exists():
+            return FAISS.load_local(vector_store_path, embeddings, allow_dangerous_deserialization=True)
+        return FAISS(
             embedding_function=embeddings,
-            index=index,
+            index=faiss.IndexFlatL2(len(embeddings.embed_query("hello world"))),
             docstore=InMemoryDocstore(),
             index_to_docstore_id={},
             )
-        store.add_documents(splits)
-        # store.save_local("../faiss_indexing")
+
+    @staticmethod
+    def _indexed_chunk_ids(store: FAISS, state: Dict[str, Dict[str, Any]], doc_id: str) -> List[str]:
+        """
+        Docstore ids of the chunks of `doc_id`, from the index state or,
+        for stores written before it was tracked, from chunk metadata.
+        """
+        indexed = set(store.index_to_docstore_id.values())
+        if doc_id in state:
+            return [i for i in state[doc_id]["chunk_ids"] if i in indexed]
+        stale = []
+        for docstore_id in indexed:
+            metadata = store.docstore.search(docstore_id).metadata
+            if doc_id in {normalize_source(v) for v in (metadata.get("doc_id"), metadata.get("source")) if v}:
+                stale.append(docstore_id)
+        return stale
+
+    def delete_documents(self, embeddings, doc_ids: List[str], vector_store_path: str) -> int:
+        """
+        Remove every chunk of `doc_ids` from the persisted store.
+
+        Returns:
+            The number of chunks deleted.
+        """
+        if not (Path(vector_store_path) / "index.faiss").exists():
+            return 0
+        store = self.open_vector_store(embeddings, vector_store_path)
+        state = self.load_index_state(vector_store_path)
+        stale = [i for doc_id in doc_ids for i in self._indexed_chunk_ids(store, state, doc_id)]
+        if stale:
+            store.delete(stale)
+            store.save_local(vector_store_path)
+        for doc_id in doc_ids:
+            state.pop(doc_id, None)
+        self.save_index_state(vector_store_path, state)
+        return len(stale)
+
+    def vector_store(
+            self,
+            embeddings,
+            splits: List[Document],
+            vector_store_path: str,
+            sources: Dict[str, Dict[str, Any]] | None = None
+            ) -> FAISS:
+        """
+        Add document splits to the FAISS store at `vector_store_path`.
+
+        The persisted store is loaded and extended (a new one is created
+        when none exists). Chunks are stored under their `chunk_id`; any
+        chunk already indexed for the documents in `splits` is deleted
+        first, so re-ingesting a changed file replaces its chunks instead
+        of duplicating them. The indexed documents are recorded in
+        `indexed_docs.json`.
+
+        Args:
+            embeddings: Embedding model instance with `embed_query`/`embed_documents` methods.
+            splits: Chunked `Document` objects with `doc_id`/`chunk_id`
+                metadata (see `splitter`).
+            vector_store_path: Filesystem path where the FAISS index is saved.
+            sources: Optional `doc_id` to extra index-state fields (e.g.
+                `sha256` of the source file).
+
+        Returns:
+            The updated store.
+
+        Raises:
+            ValueError: If the persisted index was built with embeddings of
+                another dimension.
+        """
+
+        store = self.open_vector_store(embeddings, vector_store_path)
+        state = self.load_index_state(vector_store_path)
+        splits = self.assign_chunk_ids(splits)
+
+        chunk_ids: Dict[str, List[str]] = {}
+        for chunk in splits:
+            chunk_ids.setdefault(str(chunk.metadata["doc_id"]), []).append(str(chunk.metadata["chunk_id"]))
+
+        texts = [c.page_content for c in splits]
+        vectors = embeddings.embed_documents(texts)
+        if vectors and len(vectors[0]) != store.index.d:
+            raise ValueError(
+                f"Index at {vector_store_path} has dimension {store.index.d}, "
+                f"embeddings have {len(vectors[0])}; rebuild it into a new path"
+            )
+
+        stale = [i for doc_id in chunk_ids for i in self._indexed_chunk_ids(store, state, doc_id)]
+        if stale:
+            store.delete(stale)
+        if splits:
+            store.add_embeddings(
+                zip(texts, vectors),
+                metadatas=[c.metadata for c in splits],
+                ids=[str(c.metadata["chunk_id"]) for c in splits],
+            )
         store.save_local(vector_store_path)
 
-    def indexing(self, filename: str):
+        indexed_at = time.strftime("%Y-%m-%dT%H:%M:%S%z")
+        for doc_id, ids in chunk_ids.items():
+            first = next(c for c in splits if str(c.metadata["doc_id"]) == doc_id)
+            state[doc_id] = {
+                "source": normalize_source(first.metadata["source"]) if first.metadata.get("source") else doc_id,
+                "chunk_ids": ids,
+                "indexed_at": indexed_at,
+                **((sources or {}).get(doc_id) or {}),
+            }
+        self.save_index_state(vector_store_path, state)
+        return store
+
+    def indexing(self, filename: str, embeddings=None, force: bool = False) -> int:
         """
-        High-level entry point for running the full ingestion + indexing
-        pipeline.
+        Load, split, embed and index one file into the persisted store.
 
-        This is a placeholder method intended to orchestrate `loader`,
-        `splitter`, `get_embedding_model`, and `vector_store` calls.
-        Implementations should call those helpers in sequence to create
-        and persist a vector index.
+        Files whose content hash matches the indexed version are skipped;
+        changed files have their old chunks replaced. The file is indexed
+        under its resolved path, however `filename` is spelled.
+
+        Args:
+            filename: Path of the file to index.
+            embeddings: Optional embeddings model, to reuse one across files.
+            force: Re-index even when the file is unchanged.
+
+        Returns:
+            The number of chunks indexed (0 when skipped).
         """
+        filename = normalize_source(filename)
+        sha256 = file_sha256(filename)
+        state = self.load_index_state(self.config.vector_store_path)
+        if not force and any(
+                entry.get("sha256") == sha256 and entry.get("source") == filename for entry in state.values()):
+            return 0
+
         docs = self.loader(filename=filename)
-        
+        embeddings = embeddings or self.get_embedding_model(provider=self.config.provider)
+
         all_splits = self.splitter(
             docs=docs,
             chunk_size=self.config.default_chunk_size,
-            chunk_overlap=self.config.default_chunk_overlap
+            chunk_overlap=self.config.default_chunk_overlap,
+            embeddings=embeddings,
             )
-        
-        embeddings = self.get_embedding_model(provider=self.config.provider)
-        
+        if not all_splits:
+            # nothing left to index: drop what an earlier version of the file left behind
+            self.delete_documents(embeddings, [filename], self.config.vector_store_path)
+            return 0
+
         self.vector_store(
-            embeddings=embeddings, 
-            splits=all_splits, 
-            vector_store_path=self.config.vector_store_path
+            embeddings=embeddings,
+            splits=all_splits,
+            vector_store_path=self.config.vector_store_path,
+            sources={
+                str(c.metadata["doc_id"]): {
+                    "sha256": sha256,
+                    "size": os.path.getsize(filename),
+                    "mtime": os.path.getmtime(filename),
+                }
+                for c in all_splits
+            },
             )
-        
+        return len(all_splits)
 
-    def indexing_all_dir(self, dir:str):
-        all_files = os.listdir(dir)
-        for filename in all_files:
-            ext = filename.split(".")[-1].lower()
-            if ext == "pdf":
-                self.indexing(filename=filename)
+    def plan_sync(self, dir: str) -> SyncPlan:
+        """
+        Compare the supported files under `dir` (recursively) with the
+        manifest.
+
+        Files whose size and mtime match the manifest are unchanged without
+        being read; the others are compared by content hash, so a touched
+        but identical file is not re-embedded. Files are matched by resolved
+        path (see `normalize_source`), so the plan lists resolved paths
+        however `dir` is spelled.
+        """
+        state = self.load_index_state(self.config.vector_store_path)
+        by_source = {normalize_source(entry["source"]): entry for entry in state.values() if entry.get("source")}
+        plan = SyncPlan()
+        extensions = supported_extensions()
+
+        root = Path(dir).resolve()
+        files = sorted(
+            str(p) for p in root.rglob("*")
+            if p.is_file() and p.suffix.lower() in extensions
+        )
+        for filename in files:
+            entry = by_source.get(filename)
+            if entry is None:
+                plan.added.append(filename)
+            elif entry.get("size") == os.path.getsize(filename) and entry.get("mtime") == os.path.getmtime(filename):
+                plan.unchanged.append(filename)
+            elif entry.get("sha256") == file_sha256(filename):
+                plan.unchanged.append(filename)
+            else:
+                plan.updated.append(filename)
+
+        for source in sorted(by_source):
+            path = Path(source)
+            if path.is_relative_to(root) and not path.exists():
+                plan.deleted.append(source)
+        return plan
+
+    def sync_dir(self, dir: str, dry_run: bool = False, embeddings=None) -> SyncPlan:
+        """
+        Bring the index in line with the files under `dir`: index added and
+        changed files, purge the chunks of deleted ones.
+
+        Args:
+            dir: Source directory, scanned recursively.
+            dry_run: Only compute the plan; nothing is embedded or deleted.
+            embeddings: Optional embeddings model, to reuse an existing one.
+
+        Returns:
+            The `SyncPlan` (applied unless `dry_run`).
+        """
+        plan = self.plan_sync(dir)
+        if dry_run:
+            return plan
+
+        embeddings = embeddings or self.get_embedding_model(provider=self.config.provider)
+        if plan.deleted:
+            state = self.load_index_state(self.config.vector_store_path)
+            doc_ids = [
+                doc_id for doc_id, entry in state.items()
+                if entry.get("source") and normalize_source(entry["source"]) in plan.deleted
+            ]
+            self.delete_documents(embeddings, doc_ids, self.config.vector_store_path)
+        for filename in plan.added + plan.updated:
+            self.indexing(filename=filename, embeddings=embeddings, force=True)
+
+        # record the new mtime of touched but identical files so the next sync skips hashing them
+        state = self.load_index_state(self.config.vector_store_path)
+        for entry in state.values():
+            source = normalize_source(entry["source"]) if entry.get("source") else None
+            if source in plan.unchanged:
+                entry.update(source=source, size=os.path.getsize(source), mtime=os.path.getmtime(source))
+        self.save_index_state(self.config.vector_store_path, state)
+        return plan
+
+    def indexing_all_dir(self, dir: str) -> SyncPlan:
+        """
+        Index every supported file under `dir`; see `sync_dir`.
+        """
+        return self.sync_dir(dir)
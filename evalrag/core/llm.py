@@ -0,0 +1,205 @@
+# evalrag/core/llm.py
+
+import random
+import threading
+import time
+from typing import Any, Callable, Dict, List
+
+from huggingface_hub import InferenceClient
+from openai import OpenAI
+
+from .executor import RateLimitError
+
+
+class HFClient:
+    """
+    LLM client backed by the HuggingFace Inference API.
+
+    Exposes the same `generate(prompt)` interface that `RAG` expects from
+    its `llm_client`, returning a dict with `text`, `model` and `usage`.
+
+    Args:
+        model_name: HuggingFace model repository id.
+        timeout: Request timeout in seconds.
+        max_new_tokens: Maximum number of tokens to generate.
+    """
+
+    def __init__(
+            self,
+            model_name: str = "mistralai/Mixtral-8x7B-Instruct-v0.1",
+            timeout: int = 120,
+            max_new_tokens: int = 1000
+            ) -> None:
+        self.model_name = model_name
+        self.max_new_tokens = max_new_tokens
+        self.client = InferenceClient(model=model_name, timeout=timeout)
+
+    def generate(self, prompt: str, json_mode: bool = False) -> Dict[str, Any]:
+        """
+        Send `prompt` to the model and return the generated text.
+
+        Args:
+            prompt: Fully rendered prompt string.
+            json_mode: Ask the endpoint to constrain the output to a JSON object.
+
+        Returns:
+            A dict with `text`, `model` and `usage` keys.
+        """
+        kwargs = {}
+        if json_mode:
+            kwargs["response_format"] = {"type": "json_object"}
+
+        response = self.client.chat_completion(
+            messages=[{"role": "user", "content": prompt}],
+            max_tokens=self.max_new_tokens,
+            **kwargs,
+        )
+        usage = response.usage
+        return {
+            "text": response.choices[0].message.content,
+            "model": self.model_name,
+            "usage": {
+                "prompt_tokens": getattr(usage, "prompt_tokens", None),
+                "completion_tokens": getattr(usage, "completion_tokens", None),
+            },
+        }
+
+
+class OpenAIClient:
+    """
+    LLM client backed by the OpenAI chat completions API.
+
+    Args:
+        model_name: OpenAI model name.
+        timeout: Request timeout in seconds.
+    """
+
+    def __init__(self, model_name: str = "gpt-4.1-mini", timeout: int = 120) -> None:
+        self.model_name = model_name
+        self.client = OpenAI(timeout=timeout)
+
+    def generate(self, prompt: str, json_mode: bool = False) -> Dict[str, Any]:
+        """
+        Send `prompt` to the model and return the generated text.
+
+        Args:
+            prompt: Fully rendered prompt string.
+            json_mode: Use OpenAI's JSON object response format.
+
+        Returns:
+            A dict with `text`, `model` and `usage` keys.
+        """
+        kwargs = {}
+        if json_mode:
+            kwargs["response_format"] = {"type": "json_object"}
+
+        response = self.client.chat.completions.create(
+            model=self.model_name,
+            messages=[{"role": "user", "content": prompt}],
+            **kwargs,
+        )
+        usage = response.usage
+        return {
+            "text": response.choices[0].message.content,
+            "model": response.model,
+            "usage": {
+                "prompt_tokens": getattr(usage, "prompt_tokens", None),
+                "completion_tokens": getattr(usage, "completion_tokens", None),
+            },
+        }
+
+
+class FakeLLMClient:
+    """
+    Scripted LLM client for offline runs and tests.
+
+    Responses are either taken in order from a list (cycling when
+    exhausted) or produced by a callable receiving the prompt. Every prompt
+    is recorded in `prompts` so callers can assert on what was sent.
+
+    It can also simulate a real provider under load: `latency` adds a
+    (random) delay to every call and `rate_limit_rate` makes a fraction of
+    calls fail with `RateLimitError` (HTTP 429) before producing a response.
+
+    Args:
+        responses: List of canned responses or a `prompt -> text` callable.
+        model_name: Model name reported in the response.
+        latency: Delay in seconds, or a `(min, max)` range sampled uniformly.
+        rate_limit_rate: Probability in [0, 1] of answering a call with a 429.
+        retry_after: `retry_after` seconds attached to simulated 429s.
+        seed: Seed for the latency/429 random generator.
+    """
+
+    def __init__(
+            self,
+            responses: List[str] | Callable[[str], str],
+            model_name: str = "fake",
+            latency: float | tuple = 0.0,
+            rate_limit_rate: float = 0.0,
+            retry_after: float | None = None,
+            seed: int | None = None
+            ) -> None:
+        self.responses = responses
+        self.model_name = model_name
+        self.latency = latency
+        self.rate_limit_rate = rate_limit_rate
+        self.retry_after = retry_after
+        self.prompts: List[str] = []
+        self.calls = 0
+        self.rate_limited = 0
+        self._random = random.Random(seed)
+        self._lock = threading.Lock()
+
+    def generate(self, prompt: str, json_mode: bool = False) -> Dict[str, Any]:
+        """
+        Return the next scripted response for `prompt`.
+        """
+        with self._lock:
+            self.calls += 1
+            delay = self._random.uniform(*self.latency) if isinstance(self.latency, tuple) else self.latency
+            throttled = self._random.random() < self.rate_limit_rate
+            if throttled:
+                self.rate_limited += 1
+            else:
+                self.prompts.append(prompt)
+                index = len(self.prompts) - 1
+
+        if delay:
+            time.sleep(delay)
+        if throttled:
+            raise RateLimitError("429 Too Many Requests (simulated)", retry_after=self.retry_after)
+
+        if callable(self.responses):
+            text = self.responses(prompt)
+        else:
+            text = self.responses[index % len(self.responses)]
+        return {
+            "text": text,
+            "model": self.model_name,
+            "usage": {"prompt_tokens": len(prompt.split()), "completion_tokens": len(text.split())},
+        }
+
+
+def get_llm_client(provider: str = "HF", model_name: str | None = None):
+    """
+    Return an LLM client instance for the requested provider.
+
+    Supported providers:
+      - "HF": returns an `HFClient` using the HuggingFace Inference API.
+      - "OPENAI": returns an `OpenAIClient`.
+
+    Args:
+        provider: String identifier for the provider, defaults to "HF".
+        model_name: Optional model override; each client has its own default.
+
+    Returns:
+        A client exposing `generate(prompt, json_mode=False)`.
+    """
+
+    if provider == "HF":
+        return HFClient(model_name=model_name) if model_name else HFClient()
+
+    elif provider == "OPENAI":
+        return OpenAIClient(model_name=model_name) if model_name else OpenAIClient()
+
+    raise ValueError(f"Unsupported LLM provider: {provider}")
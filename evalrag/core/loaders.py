@@ -0,0 +1,337 @@
+# evalrag/core/loaders.py
+
+"""
+Document loaders keyed by file extension and MIME type.
+
+Every loader turns one file into LangChain `Document`s with a `source`
+and `file_type` in their metadata, and keeps the structure of the format
+where it helps retrieval:
+
+- pdf: one document per page (`page`).
+- md / markdown, html / htm, docx: one document per section, with the
+  headings leading to it in `heading_path` ("Guide > Setup"); tables
+  become their own documents (`content_type: "table"`, `n_rows`).
+- csv: one document per row (`row`), written as `column: value` lines.
+- jsonl: one document per line (`line`), with the scalar fields other
+  than the text in metadata (`record_<field>` for fields named like
+  reserved metadata, e.g. `record_doc_id`).
+- txt: one document per file.
+
+Custom formats are plugged in with `register_loader`:
+
+    @register_loader(".rst", mime_types=("text/x-rst",))
+    def load_rst(path: str) -> List[Document]:
+        ...
+"""
+
+import csv
+import json
+import mimetypes
+import re
+import zipfile
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Sequence, Tuple
+from xml.etree import ElementTree
+
+from bs4 import BeautifulSoup
+from langchain_community.document_loaders import PyPDFLoader
+from langchain_core.documents import Document
+
+Loader = Callable[[str], List[Document]]
+
+LOADER_REGISTRY: Dict[str, Loader] = {}
+MIME_TYPE_REGISTRY: Dict[str, str] = {}
+
+HEADING_SEPARATOR = " > "
+_TEXT_FIELDS = ("text", "content", "body", "page_content")
+# metadata set by the loaders and `Ingestion.assign_chunk_ids`; record fields with these names are prefixed `record_`
+_RESERVED_METADATA = ("doc_id", "chunk_id", "source", "line", "file_type")
+
+
+def register_loader(*extensions: str, mime_types: Sequence[str] = ()) -> Callable[[Loader], Loader]:
+    """
+    Decorator registering a loader function for file `extensions` (with or
+    without the leading dot) and `mime_types`.
+
+    A loader takes a file path and returns its `Document`s. Registering an
+    extension again replaces its loader, so built-in loaders can be
+    overridden too.
+    """
+    def decorator(loader: Loader) -> Loader:
+        keys = [e.lower() if e.startswith(".") else f".{e.lower()}" for e in extensions]
+        for key in keys:
+            LOADER_REGISTRY[key] = loader
+        for mime_type in mime_types:
+            MIME_TYPE_REGISTRY[mime_type] = keys[0]
+        return loader
+    return decorator
+
+
+def supported_extensions() -> Tuple[str, ...]:
+    return tuple(sorted(LOADER_REGISTRY))
+
+
+def get_loader(path: str, mime_type: str | None = None) -> Loader:
+    """
+    Loader for `path`, chosen by `mime_type` when given and registered,
+    else by the file extension, else by the MIME type guessed from the name.
+
+    Raises:
+        KeyError: If no loader handles the file.
+    """
+    if mime_type in MIME_TYPE_REGISTRY:
+        return LOADER_REGISTRY[MIME_TYPE_REGISTRY[mime_type]]
+    ext = Path(path).suffix.lower()
+    if ext in LOADER_REGISTRY:
+        return LOADER_REGISTRY[ext]
+    guessed, _ = mimetypes.guess_type(str(path))
+    if guessed in MIME_TYPE_REGISTRY:
+        return LOADER_REGISTRY[MIME_TYPE_REGISTRY[guessed]]
+    raise KeyError(f"No loader for {path}; supported extensions: {', '.join(supported_extensions())}")
+
+
+def load_file(path: str, mime_type: str | None = None) -> List[Document]:
+    """
+    Load `path` with its registered loader; see `get_loader`.
+    """
+    docs = get_loader(path, mime_type)(str(path))
+    for doc in docs:
+        doc.metadata.setdefault("source", str(path))
+        doc.metadata.setdefault("file_type", Path(path).suffix.lower().lstrip("."))
+    return docs
+
+
+class _SectionBuilder:
+    """
+    Collect headings, text blocks and tables in reading order into one
+    document per section (and per table), tracking the heading path.
+    """
+
+    def __init__(self, source: str, metadata: Dict[str, Any] | None = None) -> None:
+        self.source = source
+        self.metadata = metadata or {}
+        self.headings: List[Tuple[int, str]] = []
+        self.lines: List[str] = []
+        self.docs: List[Document] = []
+
+    def _meta(self, **extra) -> Dict[str, Any]:
+        return {
+            "source": self.source,
+            **self.metadata,
+            "heading_path": HEADING_SEPARATOR.join(text for _, text in self.headings),
+            "section": len(self.docs),
+            **extra,
+        }
+
+    def flush(self) -> None:
+        text = "\n".join(self.lines).strip()
+        if text:
+            self.docs.append(Document(page_content=text, metadata=self._meta(content_type="text")))
+        self.lines = []
+
+    def heading(self, level: int, text: str, line: str | None = None) -> None:
+        self.flush()
+        while self.headings and self.headings[-1][0] >= level:
+            self.headings.pop()
+        self.headings.append((level, text.strip()))
+        self.lines.append(line if line is not None else text.strip())
+
+    def text(self, text: str) -> None:
+        if text.strip():
+            self.lines.append(text.rstrip())
+
+    def table(self, rows: List[List[str]], header: List[str] | None = None) -> None:
+        rows = [r for r in rows if any(cell.strip() for cell in r)]
+        if not rows:
+            return
+        self.flush()
+        if header:
+            lines = ["; ".join(f"{h}: {c}" for h, c in zip(header, row) if c.strip()) for row in rows]
+        else:
+            lines = [" | ".join(row) for row in rows]
+        title = self.headings[-1][1] if self.headings else ""
+        text = "\n".join(([title] if title else []) + lines)
+        self.docs.append(Document(page_content=text, metadata=self._meta(content_type="table", n_rows=len(rows))))
+
+    def documents(self) -> List[Document]:
+        self.flush()
+        return self.docs
+
+
+@register_loader(".pdf", mime_types=("application/pdf",))
+def load_pdf(path: str) -> List[Document]:
+    return PyPDFLoader(path).load()
+
+
+@register_loader(".txt", ".text", mime_types=("text/plain",))
+def load_text(path: str) -> List[Document]:
+    text = Path(path).read_text(encoding="utf-8", errors="replace")
+    return [Document(page_content=text, metadata={"source": path})] if text.strip() else []
+
+
+_MD_HEADING = re.compile(r"^(#{1,6})\s+(.*?)\s*#*\s*$")
+_MD_FENCE = re.compile(r"^\s*(```|~~~)")
+
+
+def markdown_sections(text: str, source: str, metadata: Dict[str, Any] | None = None) -> List[Document]:
+    """
+    Split Markdown `text` into one document per heading section; the
+    Markdown source (heading line included) is kept as is. Headings inside
+    fenced code blocks are ignored.
+    """
+    builder = _SectionBuilder(source, metadata)
+    in_fence = False
+    for line in text.splitlines():
+        if _MD_FENCE.match(line):
+            in_fence = not in_fence
+        match = None if in_fence else _MD_HEADING.match(line)
+        if match:
+            builder.heading(len(match.group(1)), match.group(2), line=line)
+        else:
+            builder.lines.append(line)
+    return builder.documents()
+
+
+@register_loader(".md", ".markdown", mime_types=("text/markdown", "text/x-markdown"))
+def load_markdown(path: str) -> List[Document]:
+    return markdown_sections(Path(path).read_text(encoding="utf-8", errors="replace"), path)
+
+
+_HTML_HEADINGS = ["h1", "h2", "h3", "h4", "h5", "h6"]
+_HTML_BLOCKS = ["p", "li", "pre", "blockquote", "dt", "dd", "figcaption"]
+
+
+def _html_table(table) -> Tuple[List[List[str]], List[str] | None]:
+    rows = []
+    for tr in table.find_all("tr"):
+        if tr.find_parent("table") is not table:
+            continue
+        rows.append([" ".join(cell.get_text(" ", strip=True).split()) for cell in tr.find_all(["th", "td"])])
+    header = None
+    first = table.find("tr")
+    if rows and first is not None and first.find("td") is None and first.find("th") is not None:
+        header, rows = rows[0], rows[1:]
+    return rows, header
+
+
+def html_sections(html: str, source: str, metadata: Dict[str, Any] | None = None) -> List[Document]:
+    """
+    Split an HTML page into one document per heading section of its body,
+    plus one per table. Scripts, styles and navigation are dropped; the
+    page `title` goes to metadata.
+    """
+    soup = BeautifulSoup(html, "html.parser")
+    for tag in soup(["script", "style", "nav", "header", "footer", "noscript"]):
+        tag.decompose()
+    title = soup.title.get_text(strip=True) if soup.title else ""
+    builder = _SectionBuilder(source, {**(metadata or {}), **({"title": title} if title else {})})
+    body = soup.body or soup
+
+    blocks = body.find_all(_HTML_HEADINGS + _HTML_BLOCKS + ["table"])
+    for el in blocks:
+        # nested blocks are rendered by their outermost captured ancestor
+        if el.find_parent(_HTML_BLOCKS + ["table"] + _HTML_HEADINGS) is not None:
+            continue
+        if el.name in _HTML_HEADINGS:
+            builder.heading(int(el.name[1]), el.get_text(" ", strip=True))
+        elif el.name == "table":
+            builder.table(*_html_table(el))
+        else:
+            text = el.get_text("\n" if el.name == "pre" else " ", strip=el.name != "pre")
+            builder.text(f"- {text}" if el.name == "li" else text)
+    if not blocks:
+        builder.text(body.get_text("\n", strip=True))
+    return builder.documents()
+
+
+@register_loader(".html", ".htm", mime_types=("text/html", "application/xhtml+xml"))
+def load_html(path: str) -> List[Document]:
+    return html_sections(Path(path).read_text(encoding="utf-8", errors="replace"), path)
+
+
+_W = "{http://schemas.openxmlformats.org/wordprocessingml/2006/main}"
+_DOCX_HEADING = re.compile(r"^(?:heading|titre|überschrift)\s*(\d)$", re.IGNORECASE)
+
+
+def _docx_text(element) -> str:
+    return "".join(node.text or "" for node in element.iter(f"{_W}t"))
+
+
+@register_loader(".docx", mime_types=("application/vnd.openxmlformats-officedocument.wordprocessingml.document",))
+def load_docx(path: str) -> List[Document]:
+    """
+    One document per heading section (paragraph styles `Title`,
+    `Heading 1`-`Heading 6`), plus one per table, read straight from the
+    document XML.
+    """
+    with zipfile.ZipFile(path) as archive:
+        root = ElementTree.fromstring(archive.read("word/document.xml"))
+    builder = _SectionBuilder(path)
+    body = root.find(f"{_W}body")
+    for el in (body if body is not None else []):
+        if el.tag == f"{_W}p":
+            style = el.find(f"{_W}pPr/{_W}pStyle")
+            style = (style.get(f"{_W}val") or "") if style is not None else ""
+            text = _docx_text(el)
+            match = _DOCX_HEADING.match(style)
+            if style.lower() == "title" and text.strip():
+                builder.heading(0, text)
+            elif match and text.strip():
+                builder.heading(int(match.group(1)), text)
+            else:
+                builder.text(text)
+        elif el.tag == f"{_W}tbl":
+            rows = [
+                [" ".join(_docx_text(cell).split()) for cell in tr.findall(f"{_W}tc")]
+                for tr in el.findall(f"{_W}tr")
+            ]
+            if len(rows) > 1:
+                builder.table(rows[1:], header=rows[0])
+            else:
+                builder.table(rows)
+    return builder.documents()
+
+
+@register_loader(".csv", mime_types=("text/csv",))
+def load_csv(path: str) -> List[Document]:
+    """
+    One document per row, as `column: value` lines, e.g. an FAQ's
+    question and answer.
+    """
+    docs = []
+    with open(path, "r", encoding="utf-8", errors="replace", newline="") as f:
+        for n, row in enumerate(csv.DictReader(f), start=1):
+            lines = [f"{k}: {v}" for k, v in row.items() if k and v and v.strip()]
+            if lines:
+                docs.append(Document(page_content="\n".join(lines), metadata={"source": path, "row": n}))
+    return docs
+
+
+@register_loader(".jsonl", ".ndjson", mime_types=("application/jsonl", "application/x-ndjson"))
+def load_jsonl(path: str) -> List[Document]:
+    """
+    One document per line. The text is the first of `text`, `content`,
+    `body` or `page_content`, else every string field as `key: value`
+    lines; other scalar fields go to metadata. Fields named like reserved
+    metadata (`doc_id`, `chunk_id`, `source`, ...) are kept as
+    `record_<field>`, so they do not override the ids `Ingestion` assigns.
+    """
+    docs = []
+    with open(path, "r", encoding="utf-8", errors="replace") as f:
+        for n, line in enumerate(f, start=1):
+            if not line.strip():
+                continue
+            record = json.loads(line)
+            if not isinstance(record, dict):
+                record = {"text": str(record)}
+            key = next((k for k in _TEXT_FIELDS if isinstance(record.get(k), str)), None)
+            scalars = {k: v for k, v in record.items() if k != key and isinstance(v, (str, int, float, bool))}
+            if key is not None:
+                text, metadata = record[key], scalars
+            else:
+                text = "\n".join(f"{k}: {v}" for k, v in scalars.items() if isinstance(v, str) and v.strip())
+                metadata = {k: v for k, v in scalars.items() if not isinstance(v, str)}
+            metadata = {f"record_{k}" if k in _RESERVED_METADATA else k: v for k, v in metadata.items()}
+            if text.strip():
+                docs.append(Document(page_content=text, metadata={**metadata, "source": path, "line": n}))
+    return docs
@@ -3,6 +3,10 @@
 import time
 from typing import List, Dict, Any
 from .config import load_prompt_config
+from .executor import guard_client
+from .ingestion import Ingestion
+from .llm import get_llm_client
+from .vectordb import FAISSVectorClient, QueryEmbedder
 # from core import load_core_config
 
 
@@ -76,7 +80,8 @@ class RAG:
 
         return contexts
     
-    def build_context_block(self, contexts: List[Dict], max_chars: int = 4000) -> str:
+    @staticmethod
+    def build_context_block(contexts: List[Dict], max_chars: int = 4000) -> str:
         """
         Concatenate retrieved context chunks into a single block for the prompt.
 
@@ -131,6 +136,7 @@ class RAG:
         question: str,
         source_id: str | None = None,
         top_k: int | None = None,
+        injected_text: str | None = None,
     ) -> Dict[str, Any]:
         """
         Answer a question using retrieval-augmented generation.
@@ -145,6 +151,8 @@ class RAG:
             question: The user question to answer.
             source_id: Optional filter to restrict retrieval to a specific source.
             top_k: Optional override for how many context chunks to retrieve.
+            injected_text: Optional text appended to the top retrieved chunk,
+                to test robustness to poisoned documents (prompt injection).
 
         Returns:
             A dict containing `answer`, `contexts`, and `meta` information.
@@ -156,6 +164,13 @@ class RAG:
             source_id=source_id,
             top_k=top_k,
         )
+        if injected_text:
+            top = contexts[0] if contexts else {"doc_id": "injected", "chunk_id": "injected", "score": None, "text": ""}
+            contexts = [{
+                **top,
+                "text": f"{top['text']}\n{injected_text}".strip(),
+                "metadata": {**top.get("metadata", {}), "injected": True},
+            }] + contexts[1:]
 
         # 2. build prompt
         prompt = self.build_rag_prompt(
@@ -185,4 +200,32 @@ class RAG:
         }
         return result
 
+
+def build_rag(settings, llm_client=None) -> RAG:
+    """
+    Build a `RAG` over the persisted FAISS index from `CoreSettings`.
+
+    The generator client is wrapped with `executor.guard_client` so it
+    shares the provider's rate limit with the judge.
+
+    Args:
+        settings: `CoreSettings` from `load_core_config()`.
+        llm_client: Optional generator client overriding the configured one.
+
+    Returns:
+        A ready-to-use `RAG` instance.
+    """
+    embeddings = Ingestion.get_embedding_model(provider=settings.rag.provider)
+    llm_client = llm_client or guard_client(
+        get_llm_client(provider=settings.rag.provider, model_name=settings.rag.model_name),
+        provider=settings.rag.provider,
+        execution=settings.execution,
+    )
+    return RAG(
+        vector_client=FAISSVectorClient.load(settings.rag.vector_store_path, embeddings),
+        embedder=QueryEmbedder(embeddings),
+        llm_client=llm_client,
+        top_k=settings.rag.top_k,
+    )
+
 # End of evalrag/core/rag.py
\ No newline at end of file
@@ -0,0 +1,96 @@
+# evalrag/core/vectordb.py
+
+from dataclasses import dataclass, field
+from typing import Any, Dict, List
+
+from langchain_community.vectorstores import FAISS
+
+
+@dataclass
+class SearchHit:
+    """
+    One vector search result in the shape `RAG.retrieve` reads.
+
+    Fields:
+        - `payload`: `doc_id`, `chunk_id`, `text` and `metadata` of the chunk.
+        - `score`: Similarity, higher is closer.
+    """
+    payload: Dict[str, Any] = field(default_factory=dict)
+    score: float = 0.0
+
+
+class QueryEmbedder:
+    """
+    Adapt a LangChain embeddings model to the `embed(text)` interface of `RAG`.
+
+    Args:
+        embeddings: Embeddings model exposing `embed_query`.
+    """
+
+    def __init__(self, embeddings) -> None:
+        self.embeddings = embeddings
+
+    def embed(self, text: str) -> List[float]:
+        return self.embeddings.embed_query(text)
+
+
+class FAISSVectorClient:
+    """
+    Vector search client over the FAISS index written by `Ingestion`.
+
+    Exposes the `search(embedding, top_k, filter)` interface `RAG` expects.
+    Chunks indexed before chunk ids were assigned fall back to their
+    `source` as `doc_id` and the docstore id as `chunk_id`.
+
+    Typical usage:
+      1. `client = FAISSVectorClient.load(path, embeddings)`
+      2. `RAG(vector_client=client, embedder=QueryEmbedder(embeddings), ...)`
+
+    Args:
+        store: LangChain `FAISS` vector store.
+    """
+
+    def __init__(self, store: FAISS) -> None:
+        self.store = store
+
+    @classmethod
+    def load(cls, path: str, embeddings) -> "FAISSVectorClient":
+        """
+        Load a FAISS index saved with `save_local`.
+        """
+        store = FAISS.load_local(path, embeddings, allow_dangerous_deserialization=True)
+        return cls(store)
+
+    def search(
+            self,
+            embedding: List[float],
+            top_k: int = 5,
+            filter: Dict[str, Any] | None = None
+            ) -> List[SearchHit]:
+        """
+        Return the `top_k` chunks closest to `embedding`.
+
+        Args:
+            embedding: Query embedding.
+            top_k: Number of hits.
+            filter: Optional metadata equality filter.
+
+        Returns:
+            `SearchHit`s ordered by decreasing similarity; the L2 distance of
+            the flat index is mapped to `1 / (1 + distance)`. Chunks of the
+            `sentence_window` chunker return their `window` as text.
+        """
+        results = self.store.similarity_search_with_score_by_vector(embedding, k=top_k, filter=filter)
+        hits = []
+        for doc, distance in results:
+            metadata = dict(doc.metadata)
+            hits.append(SearchHit(
+                payload={
+                    "doc_id": metadata.get("doc_id", metadata.get("source")),
+                    "chunk_id": metadata.get("chunk_id", doc.id),
+                    "text": metadata.get("window") or doc.page_content,
+                    "metadata": metadata,
+                },
+                score=1 / (1 + float(distance)),
+            ))
+        return hits
@@ -0,0 +1,30 @@
+# evalrag/datasets/__init__.py
+
+from .critique import CritiqueReport, QuestionCritic, parse_rating
+from .dedup import DedupReport, MinHasher, deduplicate, find_leakage
+from .perturbation import PERTURBATION_FAMILIES, PerturbationStats, Perturber
+from .qa_generation import QAGenerator, QAPair, build_generator, parse_qa_output, write_dataset
+from .registry import DatasetRegistry, DatasetVersion, assign_splits, content_hash, corpus_snapshot
+
+__all__ = [
+    "CritiqueReport",
+    "QuestionCritic",
+    "parse_rating",
+    "DedupReport",
+    "MinHasher",
+    "deduplicate",
+    "find_leakage",
+    "PERTURBATION_FAMILIES",
+    "PerturbationStats",
+    "Perturber",
+    "QAGenerator",
+    "QAPair",
+    "build_generator",
+    "parse_qa_output",
+    "write_dataset",
+    "DatasetRegistry",
+    "DatasetVersion",
+    "assign_splits",
+    "content_hash",
+    "corpus_snapshot",
+]
@@ -0,0 +1,176 @@
+# evalrag/datasets/critique.py
+
+"""
+Critique agents for generated QA pairs.
+
+Each question is rated 1-5 on the criteria proposed in
+https://huggingface.co/papers/2312.10003:
+
+- Groundedness: can the question be answered from the given context?
+- Relevance: is the question relevant to users of the corpus?
+- Stand-alone: is the question understandable free of any context, for
+  someone with domain knowledge or documentation access?
+
+Pairs rated below a criterion's threshold are dropped.
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Sequence, Tuple
+
+from ..core.config import load_prompt_config
+from ..core.eval.parsing import extract_json
+from ..core.executor import AsyncExecutor
+from .qa_generation import QAPair
+
+CRITIQUE_CRITERIA = ("groundedness", "relevance", "standalone")
+
+DEFAULT_THRESHOLDS = {"groundedness": 4, "relevance": 3, "standalone": 4}
+
+# unanswerable questions are by design not grounded in their seed chunk
+SKIPPED_CRITERIA = {"unanswerable": {"groundedness"}}
+
+
+def parse_rating(text: str) -> Tuple[int | None, str]:
+    """
+    Extract `(rating, rationale)` from a critique response.
+
+    Reads the JSON `rating`/`rationale` object first and falls back to a
+    `rating: <n>` line. Ratings outside 1-5 count as missing.
+    """
+    payload = extract_json(text or "")
+    rating, rationale = None, ""
+    if isinstance(payload, dict):
+        rating, rationale = payload.get("rating"), str(payload.get("rationale", "")).strip()
+    if rating is None:
+        match = re.search(r"rating\W*?(\d(?:\.\d+)?)", text or "", re.IGNORECASE)
+        rating = match.group(1) if match else None
+    try:
+        rating = int(round(float(rating)))
+    except (TypeError, ValueError):
+        return None, rationale
+    return (rating if 1 <= rating <= 5 else None), rationale
+
+
+@dataclass
+class CritiqueReport:
+    """
+    Outcome of filtering a batch of QA pairs.
+
+    Fields:
+        - `n_in` / `n_kept`: Pairs before and after filtering.
+        - `dropped_by`: Criterion to number of pairs rated below its
+          threshold (a pair failing several criteria counts for each).
+        - `unrated`: Criterion to number of pairs whose rating could not be
+          parsed; these are dropped too.
+        - `thresholds`: Thresholds applied.
+    """
+    n_in: int = 0
+    n_kept: int = 0
+    dropped_by: Dict[str, int] = field(default_factory=dict)
+    unrated: Dict[str, int] = field(default_factory=dict)
+    thresholds: Dict[str, int] = field(default_factory=dict)
+
+
+class QuestionCritic:
+    """
+    Rate generated QA pairs and drop the weak ones.
+
+    Responsibilities:
+      - Ask the LLM for a 1-5 rating and rationale per critique criterion.
+      - Store the ratings on the pair (`metadata["critique"]`).
+      - Drop pairs rated below the configured thresholds and report how
+        many pairs each filter removed.
+
+    Typical usage:
+      1. `critic = QuestionCritic(llm_client, thresholds={"groundedness": 4})`
+      2. `kept, report = critic.filter(pairs)`
+
+    Args:
+        llm_client: Client exposing `generate(prompt, json_mode=False)`.
+        prompt_config: Optional `PromptConfig`; loaded from YAML when omitted.
+        thresholds: Criterion to minimum rating; criteria left out are
+            not critiqued.
+        concurrency: Number of pairs critiqued in parallel.
+    """
+
+    def __init__(
+            self,
+            llm_client,
+            prompt_config=None,
+            thresholds: Dict[str, int] | None = None,
+            concurrency: int = 1
+            ) -> None:
+        self.llm_client = llm_client
+        self.prompt_config = prompt_config or load_prompt_config()
+        self.thresholds = dict(DEFAULT_THRESHOLDS if thresholds is None else thresholds)
+        unknown = set(self.thresholds) - set(CRITIQUE_CRITERIA)
+        if unknown:
+            raise ValueError(f"Unknown critique criteria: {', '.join(sorted(unknown))}")
+        self.executor = AsyncExecutor(concurrency=concurrency)
+
+    def _prompt(self, criterion: str, pair: QAPair) -> str:
+        template = {
+            "groundedness": self.prompt_config.question_groundedness_template,
+            "relevance": self.prompt_config.question_relevance_template,
+            "standalone": self.prompt_config.question_standalone_template,
+        }[criterion]
+        return template.format(question=pair.question, context=pair.context)
+
+    def critique(self, pair: QAPair) -> Dict[str, Dict[str, Any]]:
+        """
+        Rate one pair on every thresholded criterion that applies to its
+        question type.
+
+        Returns:
+            Criterion to `{"rating": int | None, "rationale": str}`.
+        """
+        ratings = {}
+        skipped = SKIPPED_CRITERIA.get(pair.question_type, set())
+        for criterion in self.thresholds:
+            if criterion in skipped:
+                continue
+            try:
+                output = self.llm_client.generate(self._prompt(criterion, pair), json_mode=True)["text"]
+            except Exception as e:
+                ratings[criterion] = {"rating": None, "rationale": f"{type(e).__name__}: {e}"}
+                continue
+            rating, rationale = parse_rating(output)
+            ratings[criterion] = {"rating": rating, "rationale": rationale}
+        return ratings
+
+    def filter(self, pairs: Sequence[QAPair]) -> Tuple[List[QAPair], CritiqueReport]:
+        """
+        Critique every pair and keep those meeting all thresholds.
+
+        Returns:
+            The kept pairs (with their ratings in `metadata["critique"]`)
+            and a `CritiqueReport`.
+        """
+        pairs = list(pairs)
+        critiques = self.executor.run(self.critique, pairs)
+
+        report = CritiqueReport(
+            n_in=len(pairs),
+            dropped_by={c: 0 for c in self.thresholds},
+            unrated={c: 0 for c in self.thresholds},
+            thresholds=dict(self.thresholds),
+        )
+        kept = []
+        for pair, ratings in zip(pairs, critiques):
+            pair.metadata["critique"] = ratings
+            passed = True
+            for criterion, threshold in self.thresholds.items():
+                if criterion not in ratings:
+                    continue
+                rating = ratings[criterion]["rating"]
+                if rating is None:
+                    report.unrated[criterion] += 1
+                    passed = False
+                elif rating < threshold:
+                    report.dropped_by[criterion] += 1
+                    passed = False
+            if passed:
+                kept.append(pair)
+        report.n_kept = len(kept)
+        return kept, report
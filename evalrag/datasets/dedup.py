@@ -0,0 +1,304 @@
+# evalrag/datasets/dedup.py
+
+"""
+Dataset curation: duplicate questions and chunk leakage.
+
+Questions are linked when they are exact duplicates (after normalization),
+lexical near-duplicates (MinHash estimate of the Jaccard similarity of word
+shingles) or semantic duplicates (cosine similarity of their embeddings).
+Linked questions form clusters, of which one representative is kept.
+
+Leakage is flagged per source chunk: a chunk backing many questions lets a
+pipeline tuned on some of them score well on the others for free, and a
+chunk shared by several splits leaks from one split into another.
+"""
+
+import hashlib
+import random
+import string
+from dataclasses import asdict, dataclass, field, is_dataclass
+from typing import Any, Dict, List, Sequence, Set, Tuple
+
+import numpy as np
+
+from ..core.eval.similarity import embed_texts
+
+_PUNCT = str.maketrans("", "", string.punctuation)
+_MERSENNE = (1 << 61) - 1
+_SIMILARITY_BLOCK = 1024
+
+
+def normalize_question(text: str) -> str:
+    """
+    Lowercase, drop punctuation and collapse whitespace.
+    """
+    return " ".join(str(text).lower().translate(_PUNCT).split())
+
+
+def shingles(text: str, size: int = 3) -> Set[str]:
+    """
+    Word `size`-grams of the normalized text (the whole text when shorter).
+    """
+    words = normalize_question(text).split()
+    if len(words) <= size:
+        return {" ".join(words)}
+    return {" ".join(words[i:i + size]) for i in range(len(words) - size + 1)}
+
+
+class MinHasher:
+    """
+    MinHash signatures and LSH banding for near-duplicate detection.
+
+    Args:
+        num_perm: Signature length (number of hash functions).
+        bands: Number of LSH bands; `num_perm` must be divisible by it.
+            More bands find pairs of lower similarity at the cost of more
+            candidates to verify.
+        seed: Seed of the hash functions.
+    """
+
+    def __init__(self, num_perm: int = 128, bands: int = 32, seed: int = 1) -> None:
+        if num_perm % bands:
+            raise ValueError("num_perm must be divisible by bands")
+        self.num_perm = num_perm
+        self.bands = bands
+        self.rows = num_perm // bands
+        rng = random.Random(seed)
+        self._coeffs = [(rng.randrange(1, _MERSENNE), rng.randrange(0, _MERSENNE)) for _ in range(num_perm)]
+
+    def signature(self, tokens: Set[str]) -> Tuple[int, ...]:
+        hashes = [int.from_bytes(hashlib.blake2b(t.encode("utf-8"), digest_size=8).digest(), "big") for t in tokens]
+        return tuple(min((a * h + b) % _MERSENNE for h in hashes) for a, b in self._coeffs)
+
+    @staticmethod
+    def jaccard(sig_a: Sequence[int], sig_b: Sequence[int]) -> float:
+        """
+        Estimated Jaccard similarity of two signatures.
+        """
+        return sum(x == y for x, y in zip(sig_a, sig_b)) / len(sig_a)
+
+    def candidate_pairs(self, signatures: Sequence[Tuple[int, ...]]) -> Set[Tuple[int, int]]:
+        """
+        Index pairs sharing at least one LSH band.
+        """
+        pairs: Set[Tuple[int, int]] = set()
+        for band in range(self.bands):
+            buckets: Dict[Tuple[int, ...], List[int]] = {}
+            lo = band * self.rows
+            for i, sig in enumerate(signatures):
+                buckets.setdefault(sig[lo:lo + self.rows], []).append(i)
+            for members in buckets.values():
+                for x in range(len(members)):
+                    for y in range(x + 1, len(members)):
+                        pairs.add((members[x], members[y]))
+        return pairs
+
+
+class _UnionFind:
+    def __init__(self, n: int) -> None:
+        self.parent = list(range(n))
+
+    def find(self, i: int) -> int:
+        while self.parent[i] != i:
+            self.parent[i] = self.parent[self.parent[i]]
+            i = self.parent[i]
+        return i
+
+    def union(self, i: int, j: int) -> None:
+        ri, rj = self.find(i), self.find(j)
+        if ri != rj:
+            self.parent[max(ri, rj)] = min(ri, rj)
+
+
+def similar_pairs(
+        vectors: Sequence[Sequence[float]],
+        threshold: float,
+        block_size: int = _SIMILARITY_BLOCK
+        ) -> List[Tuple[int, int]]:
+    """
+    Find the pairs of vectors whose cosine similarity reaches `threshold`.
+
+    Rows are L2-normalised (zero vectors keep a similarity of 0) and
+    compared `block_size` rows at a time, so at most
+    `block_size * len(vectors)` similarities are held in memory.
+
+    Args:
+        vectors: Embeddings, one per row.
+        threshold: Minimum cosine similarity.
+        block_size: Rows compared per matrix product.
+
+    Returns:
+        Index pairs `(i, j)` with `i < j`, sorted.
+    """
+    matrix = np.asarray(vectors, dtype=float)
+    norms = np.linalg.norm(matrix, axis=1, keepdims=True)
+    matrix = matrix / np.where(norms == 0, 1.0, norms)
+    pairs = []
+    for start in range(0, len(matrix), block_size):
+        sims = matrix[start:start + block_size] @ matrix.T
+        # keep the upper triangle of the full matrix: j > start + row
+        for row, j in np.argwhere(np.triu(sims >= threshold, start + 1)):
+            pairs.append((start + int(row), int(j)))
+    return pairs
+
+
+@dataclass
+class DedupReport:
+    """
+    Outcome of deduplicating a dataset.
+
+    Fields:
+        - `n_in` / `n_kept`: Items before and after deduplication.
+        - `links`: Number of duplicate pairs found per method (`exact`,
+          `lexical`, `semantic`).
+        - `clusters`: Duplicate clusters (two or more items), each with the
+          kept `representative` id, all `members` ids and the `methods`
+          that linked them.
+        - `leakage`: Chunk id to `n_questions` and `splits` for chunks that
+          back more than `max_questions_per_chunk` questions or appear in
+          more than one split.
+    """
+    n_in: int = 0
+    n_kept: int = 0
+    links: Dict[str, int] = field(default_factory=dict)
+    clusters: List[Dict[str, Any]] = field(default_factory=list)
+    leakage: Dict[str, Dict[str, Any]] = field(default_factory=dict)
+
+
+def _as_record(item) -> Dict[str, Any]:
+    return asdict(item) if is_dataclass(item) else dict(item)
+
+
+def _chunk_ids(record: Dict[str, Any]) -> List[str]:
+    value = record.get("chunk_id", record.get("gold_chunk_ids"))
+    if value is None:
+        return []
+    return [str(v) for v in value] if isinstance(value, (list, tuple)) else [str(value)]
+
+
+def _quality(record: Dict[str, Any]) -> float:
+    """
+    Mean critique rating of an item, used to pick cluster representatives.
+    """
+    critique = (record.get("metadata") or {}).get("critique") or {}
+    ratings = [c["rating"] for c in critique.values() if isinstance(c, dict) and c.get("rating") is not None]
+    return sum(ratings) / len(ratings) if ratings else 0.0
+
+
+def find_leakage(records: Sequence[Dict[str, Any]], max_questions_per_chunk: int = 3) -> Dict[str, Dict[str, Any]]:
+    """
+    Flag chunks backing too many questions or shared across splits.
+
+    Args:
+        records: Dataset rows with `chunk_id` and optional `split`.
+        max_questions_per_chunk: Questions a chunk may back before it is flagged.
+
+    Returns:
+        Chunk id to `{"n_questions": int, "splits": [...], "item_ids": [...]}`.
+    """
+    usage: Dict[str, Dict[str, Any]] = {}
+    for record in records:
+        for chunk_id in _chunk_ids(record):
+            entry = usage.setdefault(chunk_id, {"n_questions": 0, "splits": set(), "item_ids": []})
+            entry["n_questions"] += 1
+            entry["item_ids"].append(record.get("id"))
+            if record.get("split"):
+                entry["splits"].add(record["split"])
+    return {
+        chunk_id: {**entry, "splits": sorted(entry["splits"])}
+        for chunk_id, entry in sorted(usage.items())
+        if entry["n_questions"] > max_questions_per_chunk or len(entry["splits"]) > 1
+    }
+
+
+def deduplicate(
+        items: Sequence[Any],
+        embedder=None,
+        lexical_threshold: float = 0.8,
+        semantic_threshold: float = 0.92,
+        max_questions_per_chunk: int = 3,
+        minhasher: MinHasher | None = None
+        ) -> Tuple[List[Dict[str, Any]], DedupReport]:
+    """
+    Cluster duplicate questions and keep one representative per cluster.
+
+    Args:
+        items: Dataset rows (dicts or `QAPair`s) with `id` and `question`.
+        embedder: Optional embeddings model for semantic duplicates;
+            skipped when `None`.
+        lexical_threshold: Minimum estimated Jaccard similarity of word
+            shingles for lexical near-duplicates.
+        semantic_threshold: Minimum cosine similarity of question
+            embeddings for semantic duplicates.
+        max_questions_per_chunk: Leakage threshold, see `find_leakage`.
+        minhasher: Optional `MinHasher` overriding the default parameters.
+
+    Returns:
+        The kept rows in input order and a `DedupReport`. The
+        representative of a cluster is its member with the best mean
+        critique rating, the earliest one on ties.
+    """
+
+    records = [_as_record(item) for item in items]
+    questions = [r["question"] for r in records]
+    n = len(records)
+    uf = _UnionFind(n)
+    methods: Dict[Tuple[int, int], Set[str]] = {}
+    links = {"exact": 0, "lexical": 0, "semantic": 0}
+
+    def link(i: int, j: int, method: str) -> None:
+        pair = (min(i, j), max(i, j))
+        if method in methods.setdefault(pair, set()):
+            return
+        methods[pair].add(method)
+        links[method] += 1
+        uf.union(i, j)
+
+    first_seen: Dict[str, int] = {}
+    for i, question in enumerate(questions):
+        key = normalize_question(question)
+        if key in first_seen:
+            link(first_seen[key], i, "exact")
+        else:
+            first_seen[key] = i
+
+    minhasher = minhasher or MinHasher()
+    signatures = [minhasher.signature(shingles(q)) for q in questions]
+    for i, j in sorted(minhasher.candidate_pairs(signatures)):
+        if "exact" in methods.get((i, j), set()):
+            continue
+        if minhasher.jaccard(signatures[i], signatures[j]) >= lexical_threshold:
+            link(i, j, "lexical")
+
+    if embedder is not None and n > 1:
+        for i, j in similar_pairs(embed_texts(embedder, questions), semantic_threshold):
+            link(i, j, "semantic")
+
+    groups: Dict[int, List[int]] = {}
+    for i in range(n):
+        groups.setdefault(uf.find(i), []).append(i)
+
+    keep = set()
+    clusters = []
+    for members in groups.values():
+        best = max(members, key=lambda i: (_quality(records[i]), -i))
+        keep.add(best)
+        if len(members) > 1:
+            member_set = set(members)
+            clusters.append({
+                "representative": records[best].get("id"),
+                "members": [records[i].get("id") for i in members],
+                "methods": sorted({
+                    m for (i, j), ms in methods.items() if i in member_set and j in member_set for m in ms
+                }),
+            })
+
+    kept = [records[i] for i in range(n) if i in keep]
+    report = DedupReport(
+        n_in=n,
+        n_kept=len(kept),
+        links=links,
+        clusters=clusters,
+        leakage=find_leakage(kept, max_questions_per_chunk),
+    )
+    return kept, report
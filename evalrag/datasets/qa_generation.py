@@ -1,115 +1,354 @@
-from tqdm.auto import tqdm
-import pandas as pd
-from typing import Optional, List, Tuple
-import json
+# evalrag/datasets/qa_generation.py
+
+"""
+Build a synthetic dataset for evaluation.
+
+An LLM writes one question/answer couple per sampled chunk of our own
+ingested corpus (`Ingestion.splitter` output). Every pair records the
+chunk(s) it came from (`source_doc`, `chunk_id`, `context`), which is what
+`EvaluationRunner` reads as gold data for retrieval metrics, and its
+`question_type`, so metrics can be sliced per type:
+
+- factoid: a specific fact from one chunk.
+- multi_hop: needs one fact from each of two chunks of the same document.
+- comparison: compares things described in two chunks of different documents.
+- numerical: needs a calculation over numbers in one chunk.
+- unanswerable: on-topic but not answered by the corpus; expected answer
+  is `UNANSWERABLE_ANSWER`.
+- noisy: informal, typo-laden user-style paraphrase.
+"""
+
+import hashlib
+import logging
 import random
-import datasets
-from huggingface_hub import InferenceClient
+import re
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Sequence, Tuple
 
-pd.set_option("display.max_colwidth", None)
+from langchain_core.documents import Document
 
-from huggingface_hub import notebook_login
+from ..core.config import load_prompt_config
+from ..core.executor import AsyncExecutor, guard_client, is_retryable
+from ..core.llm import get_llm_client
+from .registry import DatasetRegistry, DatasetVersion
 
-notebook_login()
+QUESTION_TYPES = ("factoid", "multi_hop", "comparison", "numerical", "unanswerable", "noisy")
+TWO_CHUNK_TYPES = ("multi_hop", "comparison")
+UNANSWERABLE_ANSWER = "I don't know"
 
+logger = logging.getLogger(__name__)
 
-ds = datasets.load_dataset("m-ric/huggingface_doc", split="train")
 
-# 1. Build a synthetic dataset for evaluation
-# 1.1. Prepare source documents
+@dataclass
+class QAPair:
+    """
+    One generated question/answer couple.
 
-from langchain.text_splitter import RecursiveCharacterTextSplitter
-from langchain.docstore.document import Document as LangchainDocument
+    Fields match the columns `runner.load_dataset` understands:
+        - `id`: Stable id derived from the chunk id and question.
+        - `question` / `answer`: Generated couple.
+        - `context`: Text of the source chunk(s).
+        - `source_doc` / `chunk_id`: Ids of the source document(s) and
+          chunk(s); lists for two-chunk questions, empty for unanswerable
+          ones (their seed chunk is kept in `metadata`).
+        - `question_type`: One of `QUESTION_TYPES`.
+        - `metadata`: Generator details (model, prompt name).
+    """
+    id: str
+    question: str
+    answer: str
+    context: str
+    source_doc: str | List[str] | None = None
+    chunk_id: str | List[str] | None = None
+    question_type: str = "factoid"
+    metadata: Dict[str, Any] = field(default_factory=dict)
 
-langchain_docs = [
-    LangchainDocument(page_content=doc["text"], metadata={"source": doc["source"]})
-    for doc in tqdm(ds)
-]
 
+def parse_qa_output(text: str, with_answer: bool = True) -> Tuple[str, str | None] | None:
+    """
+    Extract `(question, answer)` from a generation in the prompts'
+    `Question: ... Answer: ...` format (`Factoid question:` is accepted too).
 
-text_splitter = RecursiveCharacterTextSplitter(
-    chunk_size=2000,
-    chunk_overlap=200,
-    add_start_index=True,
-    separators=["\n\n", "\n", ".", " ", ""],
-)
+    Args:
+        text: Raw model output.
+        with_answer: Whether an answer is expected; when `False` only the
+            question is read and the answer is `None`.
 
-docs_processed = []
-for doc in langchain_docs:
-    docs_processed += text_splitter.split_documents([doc])
+    Returns:
+        The stripped couple, or `None` when a required part is missing.
+    """
+    text = text or ""
+    if with_answer:
+        match = re.search(r"(?:Factoid\s+)?question:\s*(.*?)\s*Answer:\s*(.*)", text, re.DOTALL | re.IGNORECASE)
+        if not match:
+            return None
+        question, answer = match.group(1).strip(), match.group(2).split("Output:::")[0].strip()
+        return (question, answer) if question and answer else None
 
+    match = re.search(r"(?:Factoid\s+)?question:\s*(.*)", text, re.DOTALL | re.IGNORECASE)
+    question = match.group(1).split("Output:::")[0].strip() if match else ""
+    return (question, None) if question else None
 
 
-# 1.2. Setup agents for question generation
-repo_id = "mistralai/Mixtral-8x7B-Instruct-v0.1"
+def allocate_question_types(mix: Dict[str, float], n: int, rng: random.Random) -> List[str]:
+    """
+    Turn a question-type mix (weights, not necessarily normalized) into a
+    shuffled list of `n` types, using largest remainders so the counts
+    match the mix as closely as possible.
 
-llm_client = InferenceClient(
-    model=repo_id,
-    timeout=120,
-)
+    Raises:
+        ValueError: On unknown types, negative weights or an all-zero mix.
+    """
+    unknown = set(mix) - set(QUESTION_TYPES)
+    if unknown:
+        raise ValueError(f"Unknown question types: {', '.join(sorted(unknown))}")
+    if any(w < 0 for w in mix.values()) or sum(mix.values()) <= 0:
+        raise ValueError("Question type weights must be non-negative and not all zero")
 
+    total = sum(mix.values())
+    quotas = {t: n * w / total for t, w in mix.items()}
+    counts = {t: int(q) for t, q in quotas.items()}
+    for t in sorted(quotas, key=lambda t: quotas[t] - counts[t], reverse=True)[:n - sum(counts.values())]:
+        counts[t] += 1
+    types = [t for t in mix for _ in range(counts[t])]
+    rng.shuffle(types)
+    return types
 
-def call_llm(inference_client: InferenceClient, prompt: str):
-    response = inference_client.post(
-        json={
-            "inputs": prompt,
-            "parameters": {"max_new_tokens": 1000},
-            "task": "text-generation",
-        },
-    )
-    return json.loads(response.decode())[0]["generated_text"]
 
+class QAGenerator:
+    """
+    Synthetic QA testset generator over ingested chunks.
 
-call_llm(llm_client, "This is a test context")
+    Responsibilities:
+      - Sample seed chunks (reproducibly, with `seed`) and assign each a
+        question type following `question_types`.
+      - Pick a second chunk for two-chunk types: another chunk of the same
+        document for multi-hop, of another document for comparison.
+      - Ask the LLM for a question/answer couple per seed chunk,
+        `concurrency` chunks at a time.
+      - Drop unparsable generations and over-long answers.
+      - Keep the source chunk ids and question type on every pair.
 
-QA_generation_prompt = """
-Your task is to write a factoid question and an answer given a context.
-Your factoid question should be answerable with a specific, concise piece of factual information from the context.
-Your factoid question should be formulated in the same style as questions users could ask in a search engine.
-This means that your factoid question MUST NOT mention something like "according to the passage" or "context".
+    Any client exposing `generate(prompt)` works, so `FakeLLMClient` runs
+    generation fully offline.
 
-Provide your answer as follows:
+    Typical usage:
+      1. `chunks = ingestion.splitter(ingestion.loader(path))`
+      2. `pairs = QAGenerator(llm_client).generate(chunks, n_samples=50)`
+      3. `write_dataset(pairs, output_dir, name="testset")`
 
-Output:::
-Factoid question: (your factoid question)
-Answer: (your answer to the factoid question)
+    Args:
+        llm_client: Client exposing `generate(prompt)`.
+        prompt_config: Optional `PromptConfig`; loaded from YAML when omitted.
+        max_answer_chars: Answers longer than this are dropped.
+        concurrency: Number of chunks processed in parallel.
+        seed: Seed for chunk sampling and type assignment.
+        question_types: Question type to weight; factoid only by default.
+    """
 
-Now here is the context.
+    def __init__(
+            self,
+            llm_client,
+            prompt_config=None,
+            max_answer_chars: int = 300,
+            concurrency: int = 1,
+            seed: int | None = None,
+            question_types: Dict[str, float] | None = None
+            ) -> None:
+        self.llm_client = llm_client
+        self.prompt_config = prompt_config or load_prompt_config()
+        self.max_answer_chars = max_answer_chars
+        self.executor = AsyncExecutor(concurrency=concurrency)
+        self.seed = seed
+        self.question_types = dict(question_types or {"factoid": 1.0})
+        self.stats: Dict[str, Any] = {}
 
-Context: {context}\n
-Output:::"""
+    def _template(self, question_type: str) -> str:
+        return {
+            "factoid": self.prompt_config.qa_generation_template,
+            "multi_hop": self.prompt_config.qa_multi_hop_template,
+            "comparison": self.prompt_config.qa_comparison_template,
+            "numerical": self.prompt_config.qa_numerical_template,
+            "unanswerable": self.prompt_config.qa_unanswerable_template,
+            "noisy": self.prompt_config.qa_noisy_template,
+        }[question_type]
 
+    def generate_one(
+            self,
+            chunk: Document,
+            question_type: str = "factoid",
+            partner: Document | None = None
+            ) -> QAPair | None:
+        """
+        Generate a QA pair of `question_type` from one chunk.
 
-N_GENERATIONS = 10  # We intentionally generate only 10 QA couples here for cost and time considerations
+        Args:
+            chunk: Seed chunk.
+            question_type: One of `QUESTION_TYPES`.
+            partner: Second chunk for multi-hop and comparison questions.
 
-print(f"Generating {N_GENERATIONS} QA couples...")
+        Returns:
+            The pair, or `None` when the output cannot be parsed or the
+            answer is too long.
+        """
+        sources = [chunk, partner] if question_type in TWO_CHUNK_TYPES else [chunk]
+        if None in sources:
+            raise ValueError(f"{question_type} questions need a partner chunk")
+        if len(sources) == 2:
+            prompt = self._template(question_type).format(
+                context_a=chunk.page_content, context_b=partner.page_content
+            )
+        else:
+            prompt = self._template(question_type).format(context=chunk.page_content)
 
-outputs = []
-for sampled_context in tqdm(random.sample(docs_processed, N_GENERATIONS)):
-    # Generate QA couple
-    output_QA_couple = call_llm(
-        llm_client, QA_generation_prompt.format(context=sampled_context.page_content)
-    )
-    try:
-        question = output_QA_couple.split("Factoid question: ")[-1].split("Answer: ")[0]
-        answer = output_QA_couple.split("Answer: ")[-1]
-        assert len(answer) < 300, "Answer is too long"
-        outputs.append(
-            {
-                "context": sampled_context.page_content,
-                "question": question,
-                "answer": answer,
-                "source_doc": sampled_context.metadata["source"],
-            }
+        output = self.llm_client.generate(prompt)["text"]
+        unanswerable = question_type == "unanswerable"
+        parsed = parse_qa_output(output, with_answer=not unanswerable)
+        if parsed is None:
+            return None
+        question, answer = parsed
+        if unanswerable:
+            answer = UNANSWERABLE_ANSWER
+        if len(answer) > self.max_answer_chars:
+            return None
+
+        chunk_ids = [c.metadata.get("chunk_id") for c in sources]
+        doc_ids = [c.metadata.get("doc_id", c.metadata.get("source")) for c in sources]
+        key = f"{'|'.join(map(str, chunk_ids))}\n{question}".encode("utf-8")
+        metadata = {
+            "generator_model": getattr(self.llm_client, "model_name", None),
+            "prompt": "qa_generation" if question_type == "factoid" else f"qa_{question_type}",
+        }
+        if unanswerable:
+            metadata["seed_chunk_id"] = chunk_ids[0]
+            chunk_ids, doc_ids = [], []
+        return QAPair(
+            id=hashlib.sha1(key).hexdigest()[:16],
+            question=question,
+            answer=answer,
+            context="\n\n---\n\n".join(c.page_content for c in sources),
+            source_doc=doc_ids if len(sources) == 2 or unanswerable else doc_ids[0],
+            chunk_id=chunk_ids if len(sources) == 2 or unanswerable else chunk_ids[0],
+            question_type=question_type,
+            metadata=metadata,
         )
-    except:
-        continue
 
+    @staticmethod
+    def _partner(chunk: Document, question_type: str, chunks: Sequence[Document], rng: random.Random):
+        doc_id = chunk.metadata.get("doc_id")
+        others = [c for c in chunks if c is not chunk]
+        if question_type == "multi_hop":
+            preferred = [c for c in others if c.metadata.get("doc_id") == doc_id]
+        else:
+            preferred = [c for c in others if c.metadata.get("doc_id") != doc_id]
+        pool = preferred or others
+        return rng.choice(pool) if pool else None
+
+    def generate(self, chunks: Sequence[Document], n_samples: int | None = None) -> List[QAPair]:
+        """
+        Generate QA pairs from a sample of `chunks`.
+
+        Numerical questions fall back to factoid ones for seed chunks
+        without any digit, and two-chunk types fall back to factoid when
+        the corpus has a single chunk.
+
+        Args:
+            chunks: Chunked documents, e.g. from `Ingestion.splitter`.
+            n_samples: Number of seed chunks to sample; all chunks when `None`.
+
+        Returns:
+            Generated pairs in sampling order. Counts of sampled chunks,
+            kept pairs, dropped (unparsable/too long) generations, LLM
+            errors, the last error message and kept pairs per question
+            type are left in `stats`.
+
+        Raises:
+            Exception: Any failure other than a provider error that
+                survived the client's retries (`is_retryable`).
+        """
+        chunks = list(chunks)
+        rng = random.Random(self.seed)
+        seeds = chunks
+        if n_samples is not None and n_samples < len(chunks):
+            seeds = rng.sample(chunks, n_samples)
+
+        jobs = []
+        for chunk, question_type in zip(seeds, allocate_question_types(self.question_types, len(seeds), rng)):
+            if question_type == "numerical" and not re.search(r"\d", chunk.page_content):
+                question_type = "factoid"
+            partner = None
+            if question_type in TWO_CHUNK_TYPES:
+                partner = self._partner(chunk, question_type, chunks, rng)
+                if partner is None:
+                    question_type = "factoid"
+            jobs.append((chunk, question_type, partner))
+
+        def run(job):
+            try:
+                return self.generate_one(*job), None
+            except Exception as e:
+                if not is_retryable(e):
+                    raise
+                logger.warning("QA generation failed for chunk %s", job[0].metadata.get("chunk_id"), exc_info=True)
+                return None, f"{type(e).__name__}: {e}"
+
+        results = self.executor.run(run, jobs)
+        pairs = [p for p, _ in results if p is not None]
+        failures = [error for _, error in results if error is not None]
+        errors = len(failures)
+        by_type: Dict[str, int] = {}
+        for pair in pairs:
+            by_type[pair.question_type] = by_type.get(pair.question_type, 0) + 1
+        self.stats = {
+            "sampled": len(seeds),
+            "generated": len(pairs),
+            "dropped": len(seeds) - len(pairs) - errors,
+            "errors": errors,
+            "last_error": failures[-1] if failures else None,
+            "by_question_type": by_type,
+        }
+        return pairs
+
+
+def build_generator(settings, llm_client=None) -> QAGenerator:
+    """
+    Build a `QAGenerator` from `CoreSettings` (`generation` and `execution`).
+
+    Args:
+        settings: `CoreSettings` from `load_core_config()`.
+        llm_client: Optional client overriding the configured provider,
+            e.g. a `FakeLLMClient` for offline runs.
+    """
+    gen = settings.generation
+    llm_client = llm_client or guard_client(
+        get_llm_client(provider=gen.provider, model_name=gen.model_name),
+        provider=gen.provider,
+        execution=settings.execution,
+    )
+    return QAGenerator(
+        llm_client,
+        max_answer_chars=gen.max_answer_chars,
+        concurrency=settings.execution.concurrency,
+        seed=gen.seed,
+        question_types=gen.question_types,
+    )
+
+
+def write_dataset(
+        pairs: Sequence[QAPair | Dict[str, Any]],
+        output_dir: str,
+        name: str,
+        metadata: Dict[str, Any] | None = None,
+        splits: Dict[str, List[str]] | None = None
+        ) -> DatasetVersion:
+    """
+    Register `pairs` as the next minor version of dataset `name`.
 
-# 1.3. Setup critique agents
-# We will build critique agents that will rate each question on several criteria, given in this paper:
-# https://huggingface.co/papers/2312.10003
+    Thin wrapper around `DatasetRegistry(output_dir).register`, with
+    `metadata` (generator settings, sources, critique, ...) stored as the
+    version's provenance.
 
-# - Groundedness: can the question be answered from the given context?
-# - Relevance: is the question relevant to users? For instance, "What is the date when transformers 4.29.1 was released?" is not relevant for ML practitioners.
-# - Stand-alone: is the question understandable free of any context, for someone with domain knowledge/Internet access? The opposite of this would be What is the function used in this article? for a question generated from a specific blog article.
\ No newline at end of file
+    Returns:
+        The registered `DatasetVersion`; its `ref` is `name@version`.
+    """
+    return DatasetRegistry(output_dir).register(name, pairs, provenance=metadata, splits=splits)
@@ -0,0 +1,436 @@
+# evalrag/datasets/registry.py
+
+"""
+Versioned registry of evaluation datasets.
+
+Every dataset version lives in `DATA_DIR/datasets/<name>/<version>/` as a
+`data.jsonl` file next to a `manifest.json` recording its content hash,
+provenance (generator config, source corpus snapshot, critique
+thresholds, ...) and split definitions. Runs reference datasets as
+`name@version` (or just `name` for the latest version), so every score can
+be traced back to the exact items it was computed on.
+"""
+
+import hashlib
+import json
+import re
+import time
+from dataclasses import asdict, dataclass, field, is_dataclass
+from pathlib import Path
+from typing import Any, Dict, List, Sequence
+
+from ..core.config import DATA_DIR
+from ..core.ingestion import file_sha256
+
+DATA_FILE = "data.jsonl"
+MANIFEST_FILE = "manifest.json"
+VERSION_PATTERN = re.compile(r"^(\d+)\.(\d+)\.(\d+)$")
+
+
+def _records(items: Sequence[Any]) -> List[Dict[str, Any]]:
+    return [asdict(item) if is_dataclass(item) else dict(item) for item in items]
+
+
+def content_hash(records: Sequence[Dict[str, Any]]) -> str:
+    """
+    SHA-256 of the records in canonical JSON (sorted keys), independent of
+    file formatting.
+    """
+    digest = hashlib.sha256()
+    for record in records:
+        digest.update(json.dumps(record, sort_keys=True, ensure_ascii=False, default=str).encode("utf-8"))
+        digest.update(b"\n")
+    return digest.hexdigest()
+
+
+def corpus_snapshot(paths: Sequence[str]) -> List[Dict[str, Any]]:
+    """
+    Path, size and SHA-256 of each source file, for dataset provenance.
+    """
+    return [
+        {"path": str(p), "size": Path(p).stat().st_size, "sha256": file_sha256(p)}
+        for p in paths
+    ]
+
+
+def parse_ref(ref: str) -> tuple:
+    """
+    Split `name@version` into `(name, version)`; the version is `None` for
+    a bare name or `name@latest`.
+    """
+    name, _, version = ref.partition("@")
+    return name, (None if version in ("", "latest") else version)
+
+
+def _version_key(version: str) -> tuple:
+    return tuple(int(x) for x in VERSION_PATTERN.match(version).groups())
+
+
+def bump_version(version: str | None, part: str = "minor") -> str:
+    """
+    Next semantic version after `version` (`1.0.0` when there is none).
+    """
+    if version is None:
+        return "1.0.0"
+    major, minor, patch = _version_key(version)
+    if part == "major":
+        return f"{major + 1}.0.0"
+    if part == "minor":
+        return f"{major}.{minor + 1}.0"
+    if part == "patch":
+        return f"{major}.{minor}.{patch + 1}"
+    raise ValueError(f"Unknown version part: {part}")
+
+
+def _group_values(value: Any) -> List[str]:
+    if isinstance(value, (list, tuple)):
+        return [str(v) for v in value if v not in (None, "")]
+    return [] if value in (None, "") else [str(value)]
+
+
+def assign_splits(
+        records: Sequence[Any],
+        ratios: Dict[str, float],
+        seed: int = 0,
+        group_key: str | None = None
+        ) -> Dict[str, List[str]]:
+    """
+    Deterministically assign item ids to splits in the given proportions.
+
+    Items (or groups of items) are ordered by a seeded hash of their id, so
+    an item keeps its split across dataset versions as long as the ratios
+    stay the same.
+
+    Args:
+        records: Dataset rows (dicts or dataclasses) with an `id`.
+        ratios: Split name to relative share of the items.
+        seed: Seed of the ordering.
+        group_key: Optional field whose items must share a split, e.g.
+            `chunk_id` so no source chunk leaks from one split into another.
+            The field may hold a list (multi-hop items): items sharing any
+            value end up in one group, transitively. Items with an empty
+            or missing value (e.g. unanswerable questions) are grouped
+            alone.
+
+    Returns:
+        Split name to item ids.
+    """
+    total = sum(ratios.values())
+    if total <= 0:
+        raise ValueError("Split ratios must sum to a positive number")
+
+    # union-find over ("id", item id) and ("key", group value) nodes
+    parent: Dict[tuple, tuple] = {}
+
+    def find(node: tuple) -> tuple:
+        parent.setdefault(node, node)
+        while parent[node] != node:
+            parent[node] = parent[parent[node]]
+            node = parent[node]
+        return node
+
+    rows = _records(records)
+    for record in rows:
+        node = find(("id", str(record["id"])))
+        if group_key is None:
+            continue
+        for value in _group_values(record.get(group_key)):
+            other = find(("key", value))
+            if other != node:
+                parent[other] = node
+
+    groups: Dict[tuple, List[str]] = {}
+    for record in rows:
+        groups.setdefault(find(("id", str(record["id"]))), []).append(record["id"])
+    values: Dict[tuple, List[str]] = {}
+    for node in list(parent):
+        if node[0] == "key":
+            values.setdefault(find(node), []).append(node[1])
+
+    def label(root: tuple) -> str:
+        # a group is ranked by its smallest group value, or its item id when it has none
+        return min(values[root]) if root in values else root[1]
+
+    ranked = sorted(groups, key=lambda g: hashlib.sha1(f"{seed}:{label(g)}".encode("utf-8")).hexdigest())
+
+    n_items = sum(len(ids) for ids in groups.values())
+    splits: Dict[str, List[str]] = {name: [] for name in ratios}
+    names = list(ratios)
+    cumulative, target, n = 0, 0.0, 0
+    for name in names[:-1]:
+        target += n_items * ratios[name] / total
+        while n < len(ranked) and cumulative + len(groups[ranked[n]]) / 2 <= target:
+            splits[name] += groups[ranked[n]]
+            cumulative += len(groups[ranked[n]])
+            n += 1
+    for group in ranked[n:]:
+        splits[names[-1]] += groups[group]
+    return {name: sorted(ids) for name, ids in splits.items()}
+
+
+@dataclass
+class DatasetVersion:
+    """
+    One registered dataset version.
+
+    Fields:
+        - `name` / `version`: Registry coordinates; `ref` is `name@version`.
+        - `path`: The `data.jsonl` file.
+        - `content_hash`: `content_hash` of the records.
+        - `manifest`: Full manifest (provenance, splits, counts).
+    """
+    name: str
+    version: str
+    path: Path
+    content_hash: str
+    manifest: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def ref(self) -> str:
+        return f"{self.name}@{self.version}"
+
+
+class DatasetRegistry:
+    """
+    File-based registry of versioned evaluation datasets.
+
+    Responsibilities:
+      - Register datasets under a name with semantic versions, content
+        hashes, provenance and split definitions.
+      - Resolve `name@version` references and load their records, for the
+        whole dataset or one split.
+      - Import from and export to HuggingFace `datasets` directories
+        (`save_to_disk` format), offline.
+
+    Typical usage:
+      1. `registry = DatasetRegistry()`
+      2. `version = registry.register("testset", pairs, provenance={...})`
+      3. `EvaluationRunner(...).run(version.ref)`
+
+    Args:
+        root: Registry directory; `DATA_DIR/datasets` by default.
+    """
+
+    def __init__(self, root: str | None = None) -> None:
+        self.root = Path(root) if root else DATA_DIR / "datasets"
+
+    def versions(self, name: str) -> List[str]:
+        """
+        Registered versions of `name`, oldest first.
+        """
+        dataset_dir = self.root / name
+        if not dataset_dir.is_dir():
+            return []
+        versions = [
+            p.name for p in dataset_dir.iterdir()
+            if VERSION_PATTERN.match(p.name) and (p / MANIFEST_FILE).exists()
+        ]
+        return sorted(versions, key=_version_key)
+
+    def list(self) -> List[DatasetVersion]:
+        """
+        Latest version of every registered dataset.
+        """
+        if not self.root.is_dir():
+            return []
+        names = sorted(p.name for p in self.root.iterdir() if p.is_dir())
+        return [self.resolve(name) for name in names if self.versions(name)]
+
+    def resolve(self, ref: str) -> DatasetVersion:
+        """
+        Look up `name@version` (latest version for a bare name).
+
+        Raises:
+            KeyError: If the dataset or version is not registered.
+        """
+        name, version = parse_ref(ref)
+        versions = self.versions(name)
+        if not versions:
+            raise KeyError(f"Unknown dataset: {name}")
+        version = version or versions[-1]
+        if version not in versions:
+            raise KeyError(f"Unknown version {version} of dataset {name}; available: {', '.join(versions)}")
+
+        version_dir = self.root / name / version
+        with open(version_dir / MANIFEST_FILE, "r", encoding="utf-8") as f:
+            manifest = json.load(f)
+        return DatasetVersion(
+            name=name,
+            version=version,
+            path=version_dir / DATA_FILE,
+            content_hash=manifest["content_hash"],
+            manifest=manifest,
+        )
+
+    def load(self, ref: str, split: str | None = None) -> List[Dict[str, Any]]:
+        """
+        Records of a dataset version, optionally restricted to one split.
+        """
+        dataset = self.resolve(ref)
+        with open(dataset.path, "r", encoding="utf-8") as f:
+            records = [json.loads(line) for line in f if line.strip()]
+        if split is None:
+            return records
+        splits = dataset.manifest.get("splits", {})
+        if split not in splits:
+            raise KeyError(f"{dataset.ref} has no split '{split}'; available: {', '.join(splits) or 'none'}")
+        ids = set(splits[split])
+        return [r for r in records if r["id"] in ids]
+
+    def verify(self, ref: str) -> bool:
+        """
+        Whether the stored records still match the manifest's content hash.
+        """
+        dataset = self.resolve(ref)
+        return content_hash(self.load(dataset.ref)) == dataset.content_hash
+
+    def register(
+            self,
+            name: str,
+            items: Sequence[Any],
+            version: str | None = None,
+            bump: str = "minor",
+            provenance: Dict[str, Any] | None = None,
+            splits: Dict[str, List[str]] | None = None,
+            description: str = ""
+            ) -> DatasetVersion:
+        """
+        Register `items` as a new version of dataset `name`.
+
+        Registering content identical to the latest version returns that
+        version instead of creating a new one.
+
+        Args:
+            name: Dataset name.
+            items: Rows (dicts or dataclasses such as `QAPair`) with an `id`.
+            version: Explicit semantic version; defaults to bumping the
+                latest one by `bump` ("major", "minor" or "patch").
+            provenance: Free-form origin details, e.g. generator config,
+                `corpus_snapshot(...)` of the sources, critique thresholds.
+            splits: Split name to item ids; each record also gets a
+                `split` field.
+            description: Human-readable notes.
+
+        Returns:
+            The registered `DatasetVersion`.
+        """
+        if "@" in name or "/" in name:
+            raise ValueError(f"Invalid dataset name: {name}")
+        records = _records(items)
+        if splits:
+            split_of = {item_id: split for split, ids in splits.items() for item_id in ids}
+            unknown = set(split_of) - {r["id"] for r in records}
+            if unknown:
+                raise ValueError(f"Split definitions reference unknown ids: {sorted(unknown)[:5]}")
+            for record in records:
+                if record["id"] in split_of:
+                    record["split"] = split_of[record["id"]]
+
+        digest = content_hash(records)
+        existing = self.versions(name)
+        if existing and version is None:
+            latest = self.resolve(f"{name}@{existing[-1]}")
+            if latest.content_hash == digest:
+                return latest
+
+        version = version or bump_version(existing[-1] if existing else None, bump)
+        if not VERSION_PATTERN.match(version):
+            raise ValueError(f"Version must be MAJOR.MINOR.PATCH, got {version}")
+        if version in existing:
+            raise ValueError(f"{name}@{version} is already registered")
+
+        version_dir = self.root / name / version
+        version_dir.mkdir(parents=True)
+        with open(version_dir / DATA_FILE, "w", encoding="utf-8") as f:
+            for record in records:
+                f.write(json.dumps(record, ensure_ascii=False, default=str) + "\n")
+        manifest = {
+            "name": name,
+            "version": version,
+            "created_at": time.strftime("%Y-%m-%dT%H:%M:%S%z"),
+            "content_hash": digest,
+            "n_items": len(records),
+            "description": description,
+            "provenance": provenance or {},
+            "splits": splits or {},
+        }
+        with open(version_dir / MANIFEST_FILE, "w", encoding="utf-8") as f:
+            json.dump(manifest, f, indent=2, default=str)
+        return self.resolve(f"{name}@{version}")
+
+    def export_hf(self, ref: str, path: str) -> Path:
+        """
+        Save a dataset version in HuggingFace `save_to_disk` format.
+
+        Versions with splits become a `DatasetDict` with one dataset per
+        split; others a single `Dataset`. Nested fields are stored as JSON
+        strings so heterogeneous rows share one schema.
+        """
+        from datasets import Dataset, DatasetDict
+
+        dataset = self.resolve(ref)
+
+        def to_hf(records):
+            return Dataset.from_list([
+                {k: json.dumps(v) if isinstance(v, (dict, list)) else v for k, v in r.items()} for r in records
+            ])
+
+        splits = dataset.manifest.get("splits", {})
+        if splits:
+            hf = DatasetDict({split: to_hf(self.load(dataset.ref, split)) for split in splits})
+        else:
+            hf = to_hf(self.load(dataset.ref))
+        hf.save_to_disk(str(path))
+        with open(Path(path) / "evalrag_manifest.json", "w", encoding="utf-8") as f:
+            json.dump(dataset.manifest, f, indent=2, default=str)
+        return Path(path)
+
+    def import_hf(
+            self,
+            path: str,
+            name: str,
+            version: str | None = None,
+            column_map: Dict[str, str] | None = None,
+            provenance: Dict[str, Any] | None = None
+            ) -> DatasetVersion:
+        """
+        Register a HuggingFace dataset saved with `save_to_disk`.
+
+        Args:
+            path: Directory of a saved `Dataset` or `DatasetDict` (its
+                splits become registry splits).
+            name: Registry dataset name.
+            version: Optional explicit version.
+            column_map: Source column to registry field, e.g.
+                `{"query": "question", "ground_truth": "answer"}`.
+            provenance: Extra provenance merged with the import source.
+        """
+        from datasets import DatasetDict, load_from_disk
+
+        hf = load_from_disk(str(path))
+        parts = hf.items() if isinstance(hf, DatasetDict) else [(None, hf)]
+        column_map = column_map or {}
+
+        records, splits = [], {}
+        for split, part in parts:
+            for row in part:
+                record = {column_map.get(k, k): v for k, v in row.items()}
+                for key, value in record.items():
+                    if isinstance(value, str) and value[:1] in "[{":
+                        try:
+                            record[key] = json.loads(value)
+                        except json.JSONDecodeError:
+                            pass
+                record.pop("split", None)
+                if record.get("id") in (None, ""):
+                    record["id"] = hashlib.sha1(str(record.get("question")).encode("utf-8")).hexdigest()[:16]
+                records.append(record)
+                if split is not None:
+                    splits.setdefault(split, []).append(record["id"])
+        return self.register(
+            name,
+            records,
+            version=version,
+            provenance={"imported_from": str(path), **(provenance or {})},
+            splits=splits or None,
+        )
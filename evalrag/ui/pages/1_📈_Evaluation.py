@@ -1,22 +1,105 @@
-import streamlit as st
-from numpy.random import default_rng as rng
 import pandas as pd
+import streamlit as st
+
+from evalrag.core.config import load_core_config
+from evalrag.core.eval import CRITERIA, RunStore, agreement_report
+
 
+NOT_RATED = "not rated"
 
 st.title("Evaluation of RAG")
 
 
-confusion_matrix = pd.DataFrame(
-    {
-        "Predicted Cat": [85, 3, 2, 1],
-        "Predicted Dog": [2, 78, 4, 0],
-        "Predicted Bird": [1, 5, 72, 3],
-        "Predicted Fish": [0, 2, 1, 89],
-    },
-    index=["Actual Cat", "Actual Dog", "Actual Bird", "Actual Fish"],
-)
-st.table(confusion_matrix)
+@st.cache_resource
+def get_store() -> RunStore:
+    return RunStore(load_core_config().store.url)
+
+
+store = get_store()
+runs = store.list_runs()
+if not runs:
+    st.info("No evaluation runs yet. Run `EvaluationRunner` with a run store first.")
+    st.stop()
+
+run_id = st.sidebar.selectbox("Run", [r["run_id"] for r in runs])
+mode = st.sidebar.radio("Mode", ["Overview", "Annotate"])
+items = store.get_items(run_id)
+human = store.human_scores(run_id)
+
+
+if mode == "Overview":
+    run = store.get_run(run_id)
+    st.caption(
+        f"judge: {run['judge_model']} · rag: {run['rag_model']} · "
+        f"commit: {(run['git_commit'] or '-')[:8]} · annotated: {len(human)}/{len(items)}"
+    )
+
+    summary = run.get("summary") or {}
+    if summary:
+        st.subheader("Summary")
+        st.json(summary, expanded=False)
+
+    scores = store.item_scores(run_id)
+    if scores:
+        st.subheader("Scores per item")
+        df = pd.DataFrame.from_dict(scores, orient="index").sort_index()
+        st.dataframe(df[[c for c in df.columns if "." not in c or c.startswith("judge.")]])
+
+    if human:
+        st.subheader("Judge vs. human")
+        ratings, human_ratings = store.calibration_ratings(run_id)
+        report = agreement_report(ratings, human_ratings)
+        st.table(pd.DataFrame({
+            criterion: stats
+            for criterion, entry in report.items()
+            for stats in entry.get("vs_human", {}).values()
+        }).T)
+
+
+else:
+    annotator = st.sidebar.text_input("Reviewer", key="annotator")
+    show_judge = st.sidebar.checkbox("Show judge scores", value=False)
+    only_open = st.sidebar.checkbox("Only items without my annotation", value=True)
+    if not annotator:
+        st.info("Enter your reviewer name in the sidebar to start annotating.")
+        st.stop()
+
+    mine = {a["item_id"]: a for a in store.get_annotations(run_id) if a["annotator"] == annotator}
+    queue = [i for i in items if i["status"] == "ok" and not (only_open and i["item_id"] in mine)]
+    st.progress(len(mine) / len(items) if items else 0.0, text=f"{len(mine)}/{len(items)} annotated")
+    if not queue:
+        st.success("Nothing left to annotate in this run.")
+        st.stop()
+
+    item = st.selectbox("Item", queue, format_func=lambda i: f"{i['item_id']}: {i['question'][:80]}")
+    existing = mine.get(item["item_id"], {})
 
-df = pd.DataFrame(rng(0).standard_normal((20, 3)), columns=["a", "b", "c"])
+    st.markdown("**Question**")
+    st.write(item["question"])
+    st.markdown("**Retrieved contexts**")
+    for n, context in enumerate(item["contexts"] or [], start=1):
+        with st.expander(f"[{n}] {context.get('doc_id', '')} · {context.get('chunk_id', '')}"):
+            st.write(context.get("text", ""))
+    st.markdown("**Answer**")
+    st.write(item["answer"])
+    if item["reference"]:
+        with st.expander("Reference answer"):
+            st.write(item["reference"])
+    if show_judge:
+        st.json((item["judge"] or {}).get("scores", {}))
 
-st.bar_chart(df)
\ No newline at end of file
+    with st.form(f"annotation-{run_id}-{item['item_id']}"):
+        # criteria start unrated so an untouched slider is not saved as a score
+        scores = {
+            criterion: st.select_slider(
+                criterion.capitalize(),
+                options=[NOT_RATED, 1, 2, 3, 4, 5],
+                value=existing.get(criterion) or NOT_RATED,
+            )
+            for criterion in CRITERIA
+        }
+        comment = st.text_area("Comment", existing.get("comment") or "")
+        if st.form_submit_button("Save annotation"):
+            scores = {c: None if v == NOT_RATED else v for c, v in scores.items()}
+            store.add_annotation(run_id, item["item_id"], annotator, scores, comment or None)
+            st.rerun()
@@ -0,0 +1,32 @@
+# tests/fakes.py
+
+import re
+import zlib
+from typing import List
+
+DIMENSION = 256
+
+
+class FakeEmbeddings:
+    """
+    Deterministic bag-of-words embeddings for offline tests.
+
+    Every lower-cased word is hashed into one of `dimension` buckets, so
+    texts sharing words are similar and texts sharing none are orthogonal.
+    Exposes the LangChain `embed_query`/`embed_documents` interface and
+    counts the texts it embedded.
+    """
+
+    def __init__(self, dimension: int = DIMENSION) -> None:
+        self.dimension = dimension
+        self.n_embedded = 0
+
+    def embed_query(self, text: str) -> List[float]:
+        self.n_embedded += 1
+        vector = [0.0] * self.dimension
+        for word in re.findall(r"\w+", text.lower()):
+            vector[zlib.crc32(word.encode("utf-8")) % self.dimension] += 1.0
+        return vector
+
+    def embed_documents(self, texts: List[str]) -> List[List[float]]:
+        return [self.embed_query(text) for text in texts]
@@ -0,0 +1,149 @@
+# tests/test_agreement.py
+
+import json
+import math
+import tempfile
+import unittest
+from pathlib import Path
+from unittest import mock
+
+from evalrag.core.config import ExecutionConfig, load_prompt_config
+from evalrag.core.eval.agreement import (
+    agreement_report,
+    build_judges,
+    cohen_kappa,
+    fleiss_kappa,
+    krippendorff_alpha,
+    load_calibration_set,
+    score_with_judges,
+    spearman,
+)
+from evalrag.core.eval.judge import LLMJudge
+from evalrag.core.eval.metrics import Sample
+from evalrag.core.executor import GuardedClient
+from evalrag.core.llm import FakeLLMClient
+
+TEMPLATE = "Q: {question}\nA: {answer}\nC: {context_block}"
+HUMAN = {
+    "correctness": [5, 4, 2, 1],
+    "faithfulness": [5, 3, 2, 1],
+    "relevance": [4, 4, 3, 2],
+}
+SAMPLES = [Sample(question=f"question {i}", answer=f"answer {i}", contexts=[{"text": "context"}]) for i in range(4)]
+
+
+def scripted_judge(offset: int = 0) -> LLMJudge:
+    """
+    Judge answering the human scores of each sample, shifted by `offset`
+    (capped at 5).
+    """
+    responses = [
+        json.dumps({c: min(5, HUMAN[c][i] + offset) for c in HUMAN})
+        for i in range(len(SAMPLES))
+    ]
+    return LLMJudge(FakeLLMClient(responses), TEMPLATE)
+
+
+class KappaTest(unittest.TestCase):
+
+    def test_cohen_kappa(self):
+        self.assertEqual(cohen_kappa([1, 2, 3, 4, 5], [1, 2, 3, 4, 5]), 1.0)
+        # observed disagreement 0.25, expected 0.5
+        self.assertAlmostEqual(cohen_kappa([1, 1, 2, 2], [1, 2, 2, 2], weights=None, levels=(1, 2)), 0.5)
+        self.assertLess(cohen_kappa([1, 2, 4, 5], [5, 4, 2, 1]), 0)
+
+    def test_cohen_kappa_skips_missing_ratings(self):
+        self.assertEqual(cohen_kappa([1, None, 3, 5], [1, 2, None, 5]), 1.0)
+        self.assertTrue(math.isnan(cohen_kappa([1, None], [1, 2])))
+
+    def test_cohen_kappa_rejects_ratings_outside_levels(self):
+        self.assertEqual(cohen_kappa([1.0, 3.0], [1, 3]), 1.0)
+        for bad in ([2.5, 3], [0, 3], [6, 3]):
+            with self.assertRaises(ValueError):
+                cohen_kappa(bad, [3, 3])
+
+    def test_fleiss_kappa(self):
+        self.assertEqual(fleiss_kappa([[1, 1], [2, 2], [3, 3]]), 1.0)
+        self.assertLess(fleiss_kappa([[1, 2], [2, 1], [1, 2], [2, 1]]), 0)
+        self.assertTrue(math.isnan(fleiss_kappa([[1, None], [None]])))
+
+    def test_krippendorff_alpha(self):
+        self.assertEqual(krippendorff_alpha([[1, 1, None], [3, 3, 3], [5, 5]]), 1.0)
+        self.assertLess(krippendorff_alpha([[1, 5], [5, 1], [1, 5]], level="interval"), 0)
+        self.assertTrue(math.isnan(krippendorff_alpha([[1], [None, 2]])))
+
+    def test_spearman(self):
+        self.assertAlmostEqual(spearman([1, 2, 3, 4], [2, 3, 4, 5]), 1.0)
+        self.assertAlmostEqual(spearman([1, 2, 3, 4], [5, 4, 3, 2]), -1.0)
+        self.assertTrue(math.isnan(spearman([3, 3, 3], [1, 2, 3])))
+
+
+class ScoredAgreementTest(unittest.TestCase):
+
+    def test_exact_and_lenient_judges_against_humans(self):
+        ratings = score_with_judges(SAMPLES, {"exact": scripted_judge(), "lenient": scripted_judge(offset=1)})
+        self.assertEqual(ratings["exact"], HUMAN)
+        self.assertEqual(ratings["lenient"]["correctness"], [5, 5, 3, 2])
+
+        report = agreement_report(ratings, HUMAN)
+        exact = report["correctness"]["vs_human"]["exact"]
+        self.assertEqual((exact["n"], exact["exact_agreement"], exact["mae"], exact["bias"]), (4, 1.0, 0.0, 0.0))
+        self.assertEqual(exact["cohen_kappa"], 1.0)
+        lenient = report["correctness"]["vs_human"]["lenient"]
+        self.assertEqual((lenient["exact_agreement"], lenient["bias"]), (0.25, 0.75))
+        self.assertEqual(
+            set(report["correctness"]["inter_judge"]),
+            {"cohen_kappa", "spearman", "fleiss_kappa", "krippendorff_alpha"},
+        )
+
+    def test_repeats_are_separate_raters_and_bypass_the_cache(self):
+        judge = scripted_judge()
+        ratings = score_with_judges(SAMPLES, {"exact": judge}, repeats=2)
+        self.assertEqual(sorted(ratings), ["exact#1", "exact#2"])
+        self.assertEqual(judge.client.calls, 2 * len(SAMPLES))
+        # a deterministic judge agrees with itself
+        self.assertEqual(agreement_report(ratings)["faithfulness"]["inter_judge"]["cohen_kappa"], 1.0)
+
+    def test_parse_errors_are_missing_ratings(self):
+        judge = LLMJudge(FakeLLMClient(["no scores here"]), TEMPLATE)
+        ratings = score_with_judges(SAMPLES[:2], {"broken": judge})
+        self.assertEqual(ratings["broken"]["relevance"], [None, None])
+        self.assertEqual(agreement_report(ratings, HUMAN)["relevance"]["vs_human"]["broken"]["n"], 0)
+
+
+class BuildJudgesTest(unittest.TestCase):
+
+    def test_judges_are_named_and_guarded(self):
+        specs = [{"provider": "FAKE", "model": "small"}, {"provider": "FAKE"}]
+        with mock.patch("evalrag.core.eval.agreement.get_llm_client",
+                        side_effect=lambda provider, model_name: FakeLLMClient(["{}"], model_name=model_name or "fake")):
+            judges = build_judges(specs, load_prompt_config(), ExecutionConfig(), output_format="text")
+
+        self.assertEqual(sorted(judges), ["FAKE", "FAKE:small"])
+        for judge in judges.values():
+            self.assertIsInstance(judge.client, GuardedClient)
+            self.assertEqual(judge.output_format, "text")
+        self.assertEqual(judges["FAKE:small"].client.model_name, "small")
+
+
+class LoadCalibrationSetTest(unittest.TestCase):
+
+    def test_load(self):
+        rows = [
+            {"question": "q1", "answer": "a1", "contexts": ["plain text"], "human": {"correctness": 4}},
+            {"question": "q2", "answer": "a2", "contexts": [{"text": "t", "chunk_id": "d#0"}], "human": {}},
+        ]
+        with tempfile.TemporaryDirectory() as tmp:
+            path = Path(tmp) / "calibration.jsonl"
+            path.write_text("\n".join(json.dumps(r) for r in rows) + "\n\n", encoding="utf-8")
+            samples, human = load_calibration_set(str(path))
+
+        self.assertEqual([s.question for s in samples], ["q1", "q2"])
+        self.assertEqual(samples[0].contexts, [{"text": "plain text"}])
+        self.assertEqual(samples[1].contexts[0]["chunk_id"], "d#0")
+        self.assertEqual(human["correctness"], [4, None])
+        self.assertEqual(human["relevance"], [None, None])
+
+
+if __name__ == "__main__":
+    unittest.main()
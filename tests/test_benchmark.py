@@ -0,0 +1,133 @@
+# tests/test_benchmark.py
+
+import json
+import os
+import tempfile
+import unittest
+from pathlib import Path
+
+from evalrag.core.config import IngestionConfig
+from evalrag.core.eval.benchmark import (
+    BenchmarkResult,
+    ChunkingBenchmark,
+    ChunkingConfig,
+    chunking_grid,
+    passage_relevance,
+    text_overlap,
+    to_markdown_table,
+)
+from evalrag.core.eval.runner import EvalItem
+
+from .fakes import FakeEmbeddings
+
+
+def context(text, doc_id="handbook.txt"):
+    return {"doc_id": doc_id, "chunk_id": f"{doc_id}#0", "text": text}
+
+
+class GridTest(unittest.TestCase):
+
+    def test_combinations_skip_large_overlaps(self):
+        grid = chunking_grid(["recursive", "sentence_window"], [100, 400], [50, 100])
+        self.assertEqual([c.label for c in grid], [
+            "recursive/100/50", "recursive/400/50", "recursive/400/100", "sentence_window",
+        ])
+
+    def test_unknown_chunker(self):
+        with self.assertRaises(KeyError):
+            chunking_grid(["paragraph"], [100], [0])
+
+
+class PassageRelevanceTest(unittest.TestCase):
+
+    def test_text_overlap(self):
+        self.assertEqual(text_overlap("The office opens at 8 am.", "the OFFICE opens at 8 am on weekdays"), 1.0)
+        self.assertEqual(text_overlap("Remote work", "office hours"), 0.0)
+        self.assertEqual(text_overlap("", "office"), 0.0)
+        self.assertAlmostEqual(text_overlap("office opens late", "office closes late"), 2 / 3)
+
+    def test_first_matching_context_of_a_gold_document(self):
+        item = EvalItem(id="q", question="When does the office open?", gold_context="The office opens at 8 am.",
+                        gold_doc_ids=["handbook.txt"])
+        contexts = [
+            context("The office opens at 8 am.", doc_id="policies.txt"),
+            context("Remote work is allowed."),
+            context("The office opens at 8 am on weekdays."),
+            context("The office opens at 8 am."),
+        ]
+        self.assertEqual(passage_relevance(contexts, item), [False, False, True, False])
+
+    def test_gold_documents_without_a_gold_context(self):
+        item = EvalItem(id="q", question="Tell me about laptops.", gold_doc_ids=["policies.txt"])
+        contexts = [context("Remote work is allowed."), context("Laptops last three years.", doc_id="policies.txt")]
+        self.assertEqual(passage_relevance(contexts, item), [False, True])
+
+
+class MarkdownTableTest(unittest.TestCase):
+
+    def test_best_mrr_first(self):
+        results = [
+            BenchmarkResult(ChunkingConfig("recursive", 400, 50), n_chunks=12, index_bytes=2_000_000,
+                            ingest_seconds=1.23, metrics={"hit_rate@1": 0.5, "mrr": 0.6}),
+            BenchmarkResult(ChunkingConfig("sentence_window"), n_chunks=40, metrics={"hit_rate@1": 0.75, "mrr": 0.8}),
+            BenchmarkResult(ChunkingConfig("semantic", 800, 0), n_chunks=0),
+        ]
+        lines = to_markdown_table(results, [1]).split("\n")
+        self.assertEqual(lines[0], "| chunker | size | overlap | chunks | index MB | ingest s | hit@1 | MRR |")
+        self.assertEqual(lines[2], "| sentence_window | - | - | 40 | 0.00 | 0.0 | 0.750 | 0.800 |")
+        self.assertEqual(lines[3], "| recursive | 400 | 50 | 12 | 2.00 | 1.2 | 0.500 | 0.600 |")
+        self.assertEqual(lines[4], "| semantic | 800 | 0 | 0 | 0.00 | 0.0 | - | - |")
+
+
+class ChunkingBenchmarkTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.root = Path(self.tmp.name).resolve()
+        self.corpus = self.root / "corpus"
+        self.corpus.mkdir()
+        (self.corpus / "handbook.txt").write_text(
+            "The office opens at 8 am. Remote work is allowed two days per week.", encoding="utf-8")
+        (self.corpus / "policies.txt").write_text(
+            "Laptops are replaced every three years. Expense reports are due monthly.", encoding="utf-8")
+        self.dataset = self.root / "testset.jsonl"
+        records = [
+            {"id": "q1", "question": "When does the office open?", "reference": "8 am",
+             "context": "The office opens at 8 am."},
+            {"id": "q2", "question": "How often are laptops replaced?", "reference": "Every three years",
+             "source_doc": str(self.corpus / "policies.txt")},
+            {"id": "q3", "question": "What is the dress code?", "reference": "None"},
+        ]
+        self.dataset.write_text("\n".join(json.dumps(r) for r in records), encoding="utf-8")
+        self.benchmark = ChunkingBenchmark(IngestionConfig(chunker_params={}), embeddings=FakeEmbeddings(),
+                                           k_values=[4], work_dir=str(self.root))
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def test_relative_corpus_matches_resolved_gold_documents(self):
+        grid = [ChunkingConfig("sentence_window"), ChunkingConfig("recursive", 500, 0)]
+
+        results = self.benchmark.run([os.path.relpath(self.corpus)], str(self.dataset), grid)
+
+        self.assertEqual([r.config for r in results], grid)
+        self.assertEqual([r.n_chunks for r in results], [4, 2])
+        for result in results:
+            # the question without gold context or documents is not scored
+            self.assertEqual(result.n_items, 2)
+            self.assertGreater(result.index_bytes, 0)
+            self.assertEqual(result.metrics["hit_rate@4"], 1.0)
+        # temporary indexes are deleted
+        self.assertEqual(sorted(p.name for p in self.root.iterdir()), ["corpus", "testset.jsonl"])
+
+    def test_limit_and_kept_indexes(self):
+        benchmark = ChunkingBenchmark(IngestionConfig(chunker_params={}), embeddings=FakeEmbeddings(),
+                                      k_values=[1], work_dir=str(self.root), keep_indexes=True)
+        results = benchmark.run([str(self.corpus / "handbook.txt")], str(self.dataset),
+                                [ChunkingConfig("sentence_window")], limit=1)
+        self.assertEqual((results[0].n_chunks, results[0].n_items), (2, 1))
+        self.assertEqual(len(list(self.root.glob("chunking-*"))), 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
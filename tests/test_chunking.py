@@ -0,0 +1,130 @@
+# tests/test_chunking.py
+
+import unittest
+
+from langchain_core.documents import Document
+
+from evalrag.core.chunking import CHUNKER_REGISTRY, Chunker, _join_paths, get_chunker, register_chunker
+from evalrag.core.config import IngestionConfig
+from evalrag.core.eval.parsing import split_sentences
+from evalrag.core.ingestion import Ingestion
+
+from .fakes import FakeEmbeddings
+
+TWO_TOPICS = (
+    "Cats purr loudly. Cats nap all day. Cats purr while they nap. "
+    "Taxes are due in April. Taxes are filed online. Late taxes are fined."
+)
+
+
+class SplitSentencesTest(unittest.TestCase):
+
+    def test_chunkers_split_on_sentence_ends_and_blank_lines(self):
+        self.assertEqual(
+            split_sentences("Hi there. How are you? Fine!\n\nNew paragraph\nstill going", line_breaks=False),
+            ["Hi there.", "How are you?", "Fine!", "New paragraph\nstill going"],
+        )
+
+    def test_every_line_break_splits_by_default(self):
+        self.assertEqual(split_sentences("- Paris\n- Lyon. \"Nice\" too."), ["- Paris", "- Lyon.", '"Nice" too.'])
+
+    def test_lowercase_after_a_period_does_not_split(self):
+        self.assertEqual(split_sentences("Use a tool, e.g. a hammer. Done."), ["Use a tool, e.g. a hammer.", "Done."])
+
+    def test_hard_wrapped_sentences_stay_whole_in_windows(self):
+        doc = Document(page_content="The office opens\nat 8 am. It closes at 6 pm.", metadata={})
+        chunks = get_chunker("sentence_window", 10, 0).split([doc])
+        self.assertEqual([c.page_content for c in chunks], ["The office opens\nat 8 am.", "It closes at 6 pm."])
+
+
+class RegistryTest(unittest.TestCase):
+
+    def test_get_chunker(self):
+        chunker = get_chunker("sentence_window", chunk_size=50, chunk_overlap=5, params={"window_size": 1})
+        self.assertEqual((chunker.chunk_size, chunker.chunk_overlap, chunker.params), (50, 5, {"window_size": 1}))
+        with self.assertRaises(KeyError):
+            get_chunker("paragraph", chunk_size=50, chunk_overlap=5)
+        with self.assertRaises(ValueError):
+            get_chunker("semantic", chunk_size=50, chunk_overlap=5)
+
+    def test_register_chunker_needs_a_name(self):
+        with self.assertRaises(ValueError):
+            register_chunker(type("Nameless", (Chunker,), {}))
+
+    def test_builtin_chunkers(self):
+        self.assertTrue({"recursive", "token", "markdown_header", "html_header", "sentence_window", "semantic"}
+                        <= set(CHUNKER_REGISTRY))
+
+
+class SentenceWindowTest(unittest.TestCase):
+
+    def test_one_chunk_per_sentence_with_its_window(self):
+        doc = Document(page_content="One. Two. Three. Four.", metadata={"source": "notes.txt"})
+        chunks = get_chunker("sentence_window", 10, 0, params={"window_size": 1}).split([doc])
+
+        self.assertEqual([c.page_content for c in chunks], ["One.", "Two.", "Three.", "Four."])
+        self.assertEqual([c.metadata["window"] for c in chunks],
+                         ["One. Two.", "One. Two. Three.", "Two. Three. Four.", "Three. Four."])
+        self.assertEqual(chunks[0].metadata, {
+            "source": "notes.txt", "window": "One. Two.", "heading_path": "", "chunker": "sentence_window",
+        })
+
+
+class MarkdownHeaderTest(unittest.TestCase):
+
+    def test_join_paths(self):
+        self.assertEqual(_join_paths("", "Hours"), "Hours")
+        self.assertEqual(_join_paths("Guide", ""), "Guide")
+        self.assertEqual(_join_paths("Guide > Setup", "Setup > Install"), "Guide > Setup > Install")
+        self.assertEqual(_join_paths("Guide", "Setup"), "Guide > Setup")
+
+    def test_sections_extend_the_loader_heading_path(self):
+        doc = Document(
+            page_content="# Handbook\nWelcome.\n## Hours\nThe office opens at 8 am.",
+            metadata={"source": "handbook.md", "heading_path": "Handbook"},
+        )
+        chunks = get_chunker("markdown_header", chunk_size=500, chunk_overlap=0).split([doc])
+
+        self.assertEqual([c.metadata["heading_path"] for c in chunks], ["Handbook", "Handbook > Hours"])
+        self.assertEqual(chunks[1].page_content, "## Hours\nThe office opens at 8 am.")
+        self.assertEqual({c.metadata["content_type"] for c in chunks}, {"text"})
+        self.assertEqual({c.metadata["chunker"] for c in chunks}, {"markdown_header"})
+
+
+class SemanticTest(unittest.TestCase):
+
+    def test_breaks_where_the_topic_changes(self):
+        chunker = get_chunker("semantic", chunk_size=500, chunk_overlap=0, embeddings=FakeEmbeddings())
+        chunks = chunker.split([Document(page_content=TWO_TOPICS, metadata={"source": "mixed.txt"})])
+
+        self.assertEqual([c.page_content for c in chunks], [
+            "Cats purr loudly. Cats nap all day. Cats purr while they nap.",
+            "Taxes are due in April. Taxes are filed online. Late taxes are fined.",
+        ])
+        self.assertEqual({c.metadata["source"] for c in chunks}, {"mixed.txt"})
+
+    def test_short_documents_are_only_split_on_size(self):
+        embeddings = FakeEmbeddings()
+        chunker = get_chunker("semantic", chunk_size=500, chunk_overlap=0, embeddings=embeddings)
+        chunks = chunker.split([Document(page_content="Cats purr. Taxes are due.", metadata={})])
+        self.assertEqual([c.page_content for c in chunks], ["Cats purr. Taxes are due."])
+        self.assertEqual(embeddings.n_embedded, 0)
+
+
+class SplitterTest(unittest.TestCase):
+
+    def test_configured_chunker_and_params(self):
+        config = IngestionConfig(chunker="sentence_window", chunker_params={"sentence_window": {"window_size": 0}})
+        chunks = Ingestion(config).splitter([Document(page_content="One. Two.", metadata={"source": "notes.txt"})])
+
+        self.assertEqual([c.metadata["window"] for c in chunks], ["One.", "Two."])
+        self.assertEqual([c.metadata["chunk_id"].rsplit("#", 1)[1] for c in chunks], ["0", "1"])
+
+    def test_chunker_argument_overrides_the_config(self):
+        ingestion = Ingestion(IngestionConfig(chunker="recursive", chunker_params={}))
+        chunks = ingestion.splitter([Document(page_content="One. Two.", metadata={})], chunker="sentence_window")
+        self.assertEqual({c.metadata["chunker"] for c in chunks}, {"sentence_window"})
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,106 @@
+# tests/test_critique.py
+
+import json
+import unittest
+from dataclasses import replace
+
+from evalrag.core.config import load_prompt_config
+from evalrag.core.llm import FakeLLMClient
+from evalrag.datasets import QAPair, QuestionCritic, parse_rating
+
+# question to its groundedness, relevance and standalone ratings
+RATINGS = {
+    "When does the office open?": (5, 4, 5),
+    "What does the passage say about it?": (4, 3, 1),
+    "What font is the handbook set in?": (5, 1, 5),
+    "Is parking free on Sundays?": (1, 4, 5),
+}
+
+PROMPTS = replace(
+    load_prompt_config(),
+    question_groundedness_template="groundedness\nQuestion: {question}\nContext: {context}",
+    question_relevance_template="relevance\nQuestion: {question}",
+    question_standalone_template="standalone\nQuestion: {question}",
+)
+
+
+def pair(question, question_type="factoid"):
+    return QAPair(id=question[:8], question=question, answer="answer", context="The office opens at 8 am.",
+                  question_type=question_type)
+
+
+def scripted_critic(prompt: str) -> str:
+    """
+    Rate the question of a `PROMPTS` critique prompt from `RATINGS`.
+    """
+    criterion, question = prompt.split("\n")[0], next(q for q in RATINGS if q in prompt)
+    rating = RATINGS[question][("groundedness", "relevance", "standalone").index(criterion)]
+    return json.dumps({"rationale": f"rated {rating}", "rating": rating})
+
+
+class ParseRatingTest(unittest.TestCase):
+
+    def test_json(self):
+        self.assertEqual(parse_rating('{"rationale": "clear", "rating": 4}'), (4, "clear"))
+        self.assertEqual(parse_rating('```json\n{"rating": "5", "rationale": "ok"}\n```'), (5, "ok"))
+
+    def test_text_fallback(self):
+        self.assertEqual(parse_rating("Total rating: 3\nThe question is vague."), (3, ""))
+        self.assertEqual(parse_rating("rating: 4.0"), (4, ""))
+
+    def test_missing_or_out_of_range(self):
+        for text in ("", "no idea", '{"rating": 0}', '{"rating": 7, "rationale": "too high"}', '{"rating": "high"}'):
+            self.assertIsNone(parse_rating(text)[0], text)
+
+
+class QuestionCriticTest(unittest.TestCase):
+
+    def test_filter_applies_every_threshold(self):
+        client = FakeLLMClient(scripted_critic)
+        critic = QuestionCritic(client, PROMPTS, thresholds={"groundedness": 4, "relevance": 3, "standalone": 4},
+                                concurrency=2)
+        pairs = [pair(q) for q in RATINGS]
+
+        kept, report = critic.filter(pairs)
+
+        self.assertEqual([p.question for p in kept], ["When does the office open?"])
+        self.assertEqual((report.n_in, report.n_kept), (4, 1))
+        self.assertEqual(report.dropped_by, {"groundedness": 1, "relevance": 1, "standalone": 1})
+        self.assertEqual(report.unrated, {"groundedness": 0, "relevance": 0, "standalone": 0})
+        self.assertEqual(client.calls, 12)
+        self.assertTrue(all(p.metadata["critique"]["relevance"]["rationale"].startswith("rated") for p in pairs))
+
+    def test_only_thresholded_criteria_are_critiqued(self):
+        client = FakeLLMClient(scripted_critic)
+        kept, report = QuestionCritic(client, PROMPTS, thresholds={"relevance": 3}).filter([pair(q) for q in RATINGS])
+        self.assertEqual(len(kept), 3)
+        self.assertEqual(report.dropped_by, {"relevance": 1})
+        self.assertEqual(client.calls, 4)
+
+    def test_unanswerable_questions_skip_groundedness(self):
+        critic = QuestionCritic(FakeLLMClient(scripted_critic), PROMPTS)
+        kept, report = critic.filter([pair("Is parking free on Sundays?", "unanswerable")])
+        self.assertEqual(len(kept), 1)
+        self.assertNotIn("groundedness", kept[0].metadata["critique"])
+        self.assertEqual(report.dropped_by["groundedness"], 0)
+
+    def test_unparsable_and_failed_ratings_drop_the_pair(self):
+        def critic_output(prompt):
+            if prompt.startswith("relevance"):
+                raise RuntimeError("provider down")
+            return "I would rather not say."
+
+        critic = QuestionCritic(FakeLLMClient(critic_output), PROMPTS, thresholds={"groundedness": 4, "relevance": 3})
+        rated = pair("When does the office open?")
+        kept, report = critic.filter([rated])
+        self.assertEqual(kept, [])
+        self.assertEqual(report.unrated, {"groundedness": 1, "relevance": 1})
+        self.assertIn("RuntimeError", rated.metadata["critique"]["relevance"]["rationale"])
+
+    def test_unknown_criterion(self):
+        with self.assertRaises(ValueError):
+            QuestionCritic(FakeLLMClient(["{}"]), thresholds={"fluency": 3})
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,167 @@
+# tests/test_dedup.py
+
+import random
+import unittest
+
+from evalrag.core.eval.similarity import cosine_similarity
+from evalrag.datasets import MinHasher, deduplicate, find_leakage
+from evalrag.datasets.dedup import normalize_question, shingles, similar_pairs
+
+
+class ScriptedEmbeddings:
+    """
+    Embeds questions from a fixed table; unknown questions get a vector of
+    their own.
+    """
+
+    def __init__(self, vectors) -> None:
+        self.vectors = vectors
+
+    def embed_documents(self, texts):
+        return [self.vectors.get(t, [0.0, 0.0, float(i + 1)]) for i, t in enumerate(texts)]
+
+
+def item(item_id, question, chunk_id="doc#0", rating=None, split=None):
+    record = {"id": item_id, "question": question, "chunk_id": chunk_id, "metadata": {}}
+    if rating is not None:
+        record["metadata"]["critique"] = {"groundedness": {"rating": rating}, "relevance": {"rating": rating}}
+    if split:
+        record["split"] = split
+    return record
+
+
+class ShinglesTest(unittest.TestCase):
+
+    def test_normalize_question(self):
+        self.assertEqual(normalize_question("  What's the   RATE-limit?\n"), "whats the ratelimit")
+
+    def test_shingles(self):
+        self.assertEqual(shingles("How do I reset it?"), {"how do i", "do i reset", "i reset it"})
+        self.assertEqual(shingles("Reset?"), {"reset"})
+
+    def test_minhash_estimates_jaccard(self):
+        hasher = MinHasher(num_perm=256, bands=64)
+        a = {f"w{i}" for i in range(40)}
+        b = {f"w{i}" for i in range(10, 50)}
+        # true Jaccard 30 / 50
+        self.assertAlmostEqual(hasher.jaccard(hasher.signature(a), hasher.signature(b)), 0.6, delta=0.1)
+        self.assertEqual(hasher.jaccard(hasher.signature(a), hasher.signature(set(a))), 1.0)
+        with self.assertRaises(ValueError):
+            MinHasher(num_perm=100, bands=32)
+
+
+class DeduplicateTest(unittest.TestCase):
+
+    def test_exact_duplicates_after_normalization(self):
+        kept, report = deduplicate([
+            item("a", "What is the refund policy?"),
+            item("b", "what is the refund policy"),
+            item("c", "Who approves travel requests?"),
+        ])
+        self.assertEqual([r["id"] for r in kept], ["a", "c"])
+        self.assertEqual(report.links, {"exact": 1, "lexical": 0, "semantic": 0})
+        self.assertEqual(report.clusters, [{"representative": "a", "members": ["a", "b"], "methods": ["exact"]}])
+
+    def test_lexical_near_duplicates(self):
+        kept, report = deduplicate([
+            item("a", "What is the maximum upload size for a single file in the customer portal?"),
+            item("b", "What is the maximum upload size for a single file in the customer portal today?"),
+            item("c", "How long are support tickets kept before they are archived?"),
+        ])
+        self.assertEqual([r["id"] for r in kept], ["a", "c"])
+        self.assertEqual(report.links["lexical"], 1)
+        self.assertEqual(report.clusters[0]["methods"], ["lexical"])
+
+    def test_semantic_duplicates_need_an_embedder(self):
+        items = [
+            item("a", "How do I reset my password?"),
+            item("b", "What are the steps to recover account access?"),
+            item("c", "Where is the cafeteria?"),
+        ]
+        embedder = ScriptedEmbeddings({
+            "How do I reset my password?": [1.0, 0.1, 0.0],
+            "What are the steps to recover account access?": [0.95, 0.15, 0.0],
+            "Where is the cafeteria?": [0.0, 1.0, 0.0],
+        })
+
+        kept, _ = deduplicate(items)
+        self.assertEqual(len(kept), 3)
+
+        kept, report = deduplicate(items, embedder=embedder, semantic_threshold=0.95)
+        self.assertEqual([r["id"] for r in kept], ["a", "c"])
+        self.assertEqual(report.links["semantic"], 1)
+
+    def test_representative_has_the_best_critique(self):
+        kept, report = deduplicate([
+            item("a", "What is the refund policy?", rating=3),
+            item("b", "What is the refund policy?", rating=5),
+            item("c", "what is the refund policy", rating=4),
+        ])
+        self.assertEqual([r["id"] for r in kept], ["b"])
+        self.assertEqual(report.clusters[0]["representative"], "b")
+        self.assertEqual(report.clusters[0]["members"], ["a", "b", "c"])
+        self.assertEqual((report.n_in, report.n_kept), (3, 1))
+
+    def test_report_flags_leakage_of_kept_items(self):
+        kept, report = deduplicate([
+            item("a", "When does the office open?", chunk_id="doc#0"),
+            item("b", "When does the office open?", chunk_id="doc#0"),
+            item("c", "Who runs the office?", chunk_id="doc#0"),
+        ], max_questions_per_chunk=1)
+        self.assertEqual(report.leakage["doc#0"]["n_questions"], 2)
+        self.assertEqual(report.leakage["doc#0"]["item_ids"], ["a", "c"])
+
+
+class SimilarPairsTest(unittest.TestCase):
+
+    def test_blocks_match_pairwise_cosine(self):
+        rng = random.Random(0)
+        vectors = [[rng.uniform(-1, 1) for _ in range(4)] for _ in range(150)]
+        vectors[10] = [0.0] * 4
+        expected = [
+            (i, j) for i in range(150) for j in range(i + 1, 150)
+            if cosine_similarity(vectors[i], vectors[j]) >= 0.9
+        ]
+        self.assertTrue(expected)
+        self.assertEqual(similar_pairs(vectors, 0.9), expected)
+        self.assertEqual(similar_pairs(vectors, 0.9, block_size=16), expected)
+
+    def test_semantic_duplicates_in_a_large_dataset(self):
+        n = 600
+        vectors, items = {}, []
+        for i in range(n):
+            question = f"Question {i} about the handbook"
+            vectors[question] = [float(i == d) for d in range(n)]
+            items.append(item(f"q{i}", question, chunk_id=f"doc#{i}"))
+        for i in range(0, n, 100):
+            question = f"Paraphrase of question {i}"
+            vectors[question] = [float(i == d) + (0.1 if d == (i + 1) % n else 0.0) for d in range(n)]
+            items.append(item(f"p{i}", question, chunk_id=f"doc#{i}"))
+
+        kept, report = deduplicate(items, embedder=ScriptedEmbeddings(vectors))
+
+        self.assertEqual(len(kept), n)
+        self.assertEqual(report.links, {"exact": 0, "lexical": 0, "semantic": 6})
+        self.assertEqual([c["members"] for c in report.clusters], [[f"q{i}", f"p{i}"] for i in range(0, n, 100)])
+
+
+class FindLeakageTest(unittest.TestCase):
+
+    def test_questions_per_chunk_and_split_sharing(self):
+        records = [
+            item("a", "q1", chunk_id="doc#0", split="dev"),
+            item("b", "q2", chunk_id=["doc#0", "doc#1"], split="test"),
+            item("c", "q3", chunk_id="doc#1", split="test"),
+            item("d", "q4", chunk_id="doc#2"),
+            item("e", "q5", chunk_id="doc#2"),
+            item("f", "q6", chunk_id="doc#2"),
+            item("g", "q7", chunk_id=[]),
+        ]
+        leakage = find_leakage(records, max_questions_per_chunk=2)
+        self.assertEqual(sorted(leakage), ["doc#0", "doc#2"])
+        self.assertEqual(leakage["doc#0"], {"n_questions": 2, "splits": ["dev", "test"], "item_ids": ["a", "b"]})
+        self.assertEqual((leakage["doc#2"]["n_questions"], leakage["doc#2"]["splits"]), (3, []))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,156 @@
+# tests/test_executor.py
+
+import threading
+import time
+import unittest
+
+from evalrag.core.executor import AsyncExecutor, GuardedClient, RateLimiter, RateLimitError
+from evalrag.core.llm import FakeLLMClient
+
+
+class FlakyClient:
+    """
+    Fake provider that sleeps `latency` seconds per call and answers the
+    first `n_rate_limited` calls with a 429. `peak` is the largest number
+    of calls that ran at once.
+    """
+
+    def __init__(self, n_rate_limited: int = 0, latency: float = 0.0, retry_after: float | None = None) -> None:
+        self.n_rate_limited = n_rate_limited
+        self.latency = latency
+        self.retry_after = retry_after
+        self.calls = 0
+        self.call_times = []
+        self.running = 0
+        self.peak = 0
+        self._lock = threading.Lock()
+
+    def generate(self, prompt, **kwargs):
+        with self._lock:
+            self.calls += 1
+            self.call_times.append(time.monotonic())
+            throttled = self.calls <= self.n_rate_limited
+            self.running += 1
+            self.peak = max(self.peak, self.running)
+        time.sleep(self.latency)
+        with self._lock:
+            self.running -= 1
+        if throttled:
+            raise RateLimitError("429 Too Many Requests", retry_after=self.retry_after)
+        return {"text": f"answer to {prompt}", "model": "flaky", "usage": {}}
+
+
+def guarded(client, **kwargs) -> GuardedClient:
+    options = {"max_retries": 3, "timeout_s": 2, "backoff_min_s": 0.01, "backoff_max_s": 0.02}
+    return GuardedClient(client, **{**options, **kwargs})
+
+
+class GuardedClientTest(unittest.TestCase):
+    def test_rate_limited_calls_are_retried(self):
+        client = FlakyClient(n_rate_limited=2, latency=0.01)
+        self.assertEqual(guarded(client).generate("q")["text"], "answer to q")
+        self.assertEqual(client.calls, 3)
+
+    def test_retry_after_pauses_the_limiter(self):
+        client = FlakyClient(n_rate_limited=1, retry_after=0.3)
+        guarded(client).generate("q")
+        self.assertGreaterEqual(client.call_times[1] - client.call_times[0], 0.28)
+
+    def test_gives_up_after_max_retries(self):
+        client = FlakyClient(n_rate_limited=100)
+        with self.assertRaises(RateLimitError):
+            guarded(client, max_retries=2).generate("q")
+        self.assertEqual(client.calls, 3)
+
+    def test_non_retryable_errors_are_raised_at_once(self):
+        class BrokenClient:
+            calls = 0
+
+            def generate(self, prompt, **kwargs):
+                BrokenClient.calls += 1
+                raise ValueError("bad request")
+
+        with self.assertRaises(ValueError):
+            guarded(BrokenClient()).generate("q")
+        self.assertEqual(BrokenClient.calls, 1)
+
+    def test_slow_calls_time_out_and_are_retried(self):
+        client = FakeLLMClient(["late"], latency=0.5)
+        started = time.monotonic()
+        with self.assertRaises(TimeoutError):
+            guarded(client, max_retries=1, timeout_s=0.05).generate("q")
+        self.assertLess(time.monotonic() - started, 0.45)
+        self.assertEqual(client.calls, 2)
+
+    def test_timed_out_calls_do_not_grow_the_pool(self):
+        client = FlakyClient(latency=0.3)
+        guarded_client = guarded(client, max_retries=0, timeout_s=0.02, max_workers=2)
+        errors = []
+
+        def call():
+            try:
+                guarded_client.generate("q")
+            except TimeoutError as e:
+                errors.append(e)
+
+        callers = [threading.Thread(target=call) for _ in range(6)]
+        for thread in callers:
+            thread.start()
+        for thread in callers:
+            thread.join()
+        self.assertEqual(len(errors), 6)
+        self.assertLessEqual(client.peak, 2)
+
+    def test_fake_provider_under_load(self):
+        client = FakeLLMClient(["ok"], latency=(0.001, 0.005), rate_limit_rate=0.3, seed=7)
+        guarded_client = guarded(client, max_retries=10)
+        results = AsyncExecutor(concurrency=4).run(lambda i: guarded_client.generate(f"q{i}")["text"], range(20))
+        self.assertEqual(results, ["ok"] * 20)
+        self.assertGreater(client.rate_limited, 0)
+        self.assertEqual(client.calls, 20 + client.rate_limited)
+
+
+class RateLimiterTest(unittest.TestCase):
+    def test_requests_per_minute_hold_under_concurrency(self):
+        limiter = RateLimiter(requests_per_minute=600)
+        for _ in range(600):
+            limiter.acquire()  # drain the initial burst: 10 requests per second remain
+        client = FlakyClient()
+        guarded_client = guarded(client, limiter=limiter)
+        started = time.monotonic()
+        AsyncExecutor(concurrency=5).run(lambda i: guarded_client.generate(str(i)), range(10))
+        self.assertGreaterEqual(time.monotonic() - started, 0.9)
+        gaps = [b - a for a, b in zip(client.call_times, client.call_times[1:])]
+        self.assertGreaterEqual(min(gaps), 0.05)
+
+    def test_tokens_per_minute(self):
+        limiter = RateLimiter(tokens_per_minute=6000)
+        limiter.acquire(tokens=6000)
+        started = time.monotonic()
+        limiter.acquire(tokens=20)  # 100 tokens per second
+        self.assertGreaterEqual(time.monotonic() - started, 0.18)
+
+
+class AsyncExecutorTest(unittest.TestCase):
+    def test_bounded_concurrency_and_input_order(self):
+        running, peak, lock = [0], [0], threading.Lock()
+        reported = []
+
+        def work(i):
+            with lock:
+                running[0] += 1
+                peak[0] = max(peak[0], running[0])
+            time.sleep(0.02)
+            with lock:
+                running[0] -= 1
+            return i * i
+
+        results = AsyncExecutor(concurrency=3).run(work, range(10), on_result=reported.append)
+        self.assertEqual(results, [i * i for i in range(10)])
+        self.assertEqual(sorted(reported), results)
+        self.assertLessEqual(peak[0], 3)
+        self.assertGreater(peak[0], 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
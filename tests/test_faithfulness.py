@@ -0,0 +1,66 @@
+# tests/test_faithfulness.py
+
+import json
+import unittest
+
+from evalrag.core.config import load_prompt_config
+from evalrag.core.eval.faithfulness import ClaimFaithfulnessMetric
+from evalrag.core.eval.metrics import METRIC_OK, METRIC_PARSE_ERROR, METRIC_SKIPPED, Sample
+from evalrag.core.eval.parsing import extract_list, parse_verdicts
+from evalrag.core.llm import FakeLLMClient
+
+CONTEXTS = [{"text": "Paris is the capital of France. It has 2.1 million inhabitants."}]
+SAMPLE = Sample(question="Tell me about Paris.", answer="Paris is the capital of France and has 5 million inhabitants.",
+                contexts=CONTEXTS)
+CLAIMS = json.dumps({"claims": ["Paris is the capital of France.", "Paris has 5 million inhabitants."]})
+
+
+def metric(responses) -> ClaimFaithfulnessMetric:
+    return ClaimFaithfulnessMetric(llm=FakeLLMClient(responses), prompt_config=load_prompt_config())
+
+
+class ClaimFaithfulnessTest(unittest.TestCase):
+    def test_fraction_of_supported_claims(self):
+        verdicts = json.dumps({"verdicts": [
+            {"claim": 1, "supported": True, "reason": "first sentence"},
+            {"claim": 2, "supported": False, "reason": "context says 2.1 million"},
+        ]})
+        m = metric([CLAIMS, verdicts])
+        result = m.score(SAMPLE)
+        self.assertEqual(result.status, METRIC_OK)
+        self.assertEqual(result.score, 0.5)
+        self.assertEqual(result.details["unsupported_claims"], ["Paris has 5 million inhabitants."])
+        self.assertIn("2.1 million inhabitants", m.llm.prompts[1])
+
+    def test_verdict_lines_fallback(self):
+        result = metric([CLAIMS, "1. yes - stated\n2. no - wrong number"]).score(SAMPLE)
+        self.assertEqual(result.score, 0.5)
+        self.assertEqual(result.details["claims"][1]["reason"], "wrong number")
+
+    def test_answer_without_claims_is_skipped(self):
+        result = metric(['{"claims": []}']).score(SAMPLE)
+        self.assertEqual(result.status, METRIC_SKIPPED)
+        self.assertIsNone(result.score)
+
+    def test_missing_verdict_is_a_parse_error(self):
+        result = metric([CLAIMS, '{"verdicts": [{"claim": 1, "supported": true}]}']).score(SAMPLE)
+        self.assertEqual(result.status, METRIC_PARSE_ERROR)
+        self.assertEqual(result.details["unverified_claims"], ["Paris has 5 million inhabitants."])
+
+    def test_needs_answer_and_contexts(self):
+        m = metric([CLAIMS])
+        self.assertEqual(m.score(Sample(question="q", answer="a")).status, METRIC_SKIPPED)
+        self.assertEqual(m.llm.calls, 0)
+
+
+class ParsingTest(unittest.TestCase):
+    def test_extract_list_from_bullets(self):
+        self.assertEqual(extract_list("Claims:\n- one\n2) two\n* three", "claims"), ["one", "two", "three"])
+
+    def test_parse_verdicts_drops_out_of_range_and_unknown(self):
+        text = json.dumps([{"id": 1, "verdict": "yes"}, {"id": 7, "verdict": "no"}, {"id": 2, "verdict": "maybe"}])
+        self.assertEqual(parse_verdicts(text, 3), {1: {"verdict": True, "reason": ""}})
+
+
+if __name__ == "__main__":
+    unittest.main()
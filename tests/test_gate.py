@@ -0,0 +1,191 @@
+# tests/test_gate.py
+
+import io
+import tempfile
+import unittest
+import xml.etree.ElementTree as ET
+from contextlib import redirect_stderr
+from pathlib import Path
+from types import SimpleNamespace
+
+import yaml
+
+from evalrag import cli
+from evalrag.core.config import CONFIG_FILE, _get
+from evalrag.core.eval.gate import (
+    GateCheck,
+    GateReport,
+    check_policy,
+    load_policy,
+    resolve_threshold,
+    summary_value,
+    to_junit_xml,
+    to_markdown,
+)
+
+SUMMARY = {
+    "n_errors": 0,
+    "judge": {"hallucination_rate": 0.1, "criteria": {"faithfulness": 4.2}},
+    "metrics": {"ir.hit_rate@5": 0.8, "context_recall": float("nan")},
+}
+
+
+def comparison(regressions=0, significant=None):
+    """
+    `RunComparison`-like object with `regressions` item regressions and
+    the given metric to mean delta of significantly changed metrics.
+    """
+    return SimpleNamespace(
+        base_run_id="base",
+        regressions=[{"item_id": f"q{i}"} for i in range(regressions)],
+        significance={m: {"significant": True, "mean_delta": d} for m, d in (significant or {}).items()},
+        metric_deltas={"composite": {"base_mean": 4.0, "candidate_mean": 3.5, "delta": -0.5}},
+    )
+
+
+class LoadPolicyTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.path = Path(self.tmp.name) / "policy.yaml"
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def write(self, policy):
+        self.path.write_text(yaml.safe_dump(policy), encoding="utf-8")
+        return str(self.path)
+
+    def test_suite_overrides_defaults(self):
+        path = self.write({
+            "defaults": {"thresholds": [{"metric": "n_errors", "max": 0}], "limit": 10},
+            "suites": {"smoke": {"dataset": "smoke.jsonl", "limit": 5}, "full": None},
+        })
+        self.assertEqual(load_policy(path, "smoke"), {
+            "thresholds": [{"metric": "n_errors", "max": 0}], "limit": 5, "dataset": "smoke.jsonl",
+        })
+        self.assertEqual(load_policy(path, "full", require_dataset=False)["limit"], 10)
+
+    def test_baseline_is_merged_key_by_key(self):
+        path = self.write({
+            "defaults": {"baseline": {"run_id": None, "tolerance": 0.1, "max_regressions": 3}},
+            "suites": {"smoke": {"dataset": "smoke.jsonl", "baseline": {"run_id": "base", "tolerance": 0.2}},
+                       "solo": {"dataset": "smoke.jsonl", "baseline": None}},
+        })
+        self.assertEqual(load_policy(path, "smoke")["baseline"],
+                         {"run_id": "base", "tolerance": 0.2, "max_regressions": 3})
+        # a suite may still switch the baseline off altogether
+        self.assertIsNone(load_policy(path, "solo")["baseline"])
+
+    def test_suite_without_dataset(self):
+        path = self.write({"suites": {"smoke": {"thresholds": [{"metric": "n_errors", "max": 0}]}}})
+        with self.assertRaises(KeyError):
+            load_policy(path, "smoke")
+        self.assertNotIn("dataset", load_policy(path, "smoke", require_dataset=False))
+        stderr = io.StringIO()
+        with redirect_stderr(stderr):
+            self.assertEqual(cli.main(["gate", "--suite", "smoke", "--policy", path]), cli.EXIT_USAGE)
+        self.assertIn("has no dataset", stderr.getvalue())
+
+    def test_unknown_suite(self):
+        with self.assertRaises(KeyError):
+            load_policy(self.write({"suites": {"smoke": {}}}), "nightly")
+
+    def test_bad_threshold_fails_at_load(self):
+        path = self.write({"suites": {"smoke": {
+            "dataset": "smoke.jsonl", "thresholds": [{"metric": "x", "min": "eval.no_such_setting"}],
+        }}})
+        with self.assertRaises(ValueError):
+            load_policy(path, "smoke")
+
+    def test_gate_command_rejects_bad_threshold(self):
+        path = self.write({"suites": {"smoke": {"dataset": "smoke.jsonl", "thresholds": [{"metric": "x", "max": [1, 2]}]}}})
+        stderr = io.StringIO()
+        with redirect_stderr(stderr):
+            self.assertEqual(cli.main(["gate", "--suite", "smoke", "--policy", path]), cli.EXIT_USAGE)
+            self.assertEqual(cli.main(["gate", "--suite", "nightly", "--policy", path]), cli.EXIT_USAGE)
+        self.assertIn("is not a number", stderr.getvalue())
+        self.assertIn("Unknown suite 'nightly'", stderr.getvalue())
+
+
+class ThresholdTest(unittest.TestCase):
+
+    def test_resolve_threshold(self):
+        self.assertEqual(resolve_threshold(0.05), 0.05)
+        self.assertEqual(resolve_threshold(1), 1.0)
+        self.assertEqual(
+            resolve_threshold("eval.faithfulness_threshold"),
+            float(_get(CONFIG_FILE, "eval.faithfulness_threshold")),
+        )
+        for bad in ("eval.no_such_setting", None, "eval"):
+            with self.assertRaises(ValueError):
+                resolve_threshold(bad)
+
+    def test_summary_value_with_dotted_keys(self):
+        self.assertEqual(summary_value(SUMMARY, "judge.criteria.faithfulness"), 4.2)
+        self.assertEqual(summary_value(SUMMARY, "metrics.ir.hit_rate@5"), 0.8)
+        self.assertIsNone(summary_value(SUMMARY, "judge.criteria.relevance"))
+        self.assertIsNone(summary_value(SUMMARY, "n_errors.total"))
+
+
+class CheckPolicyTest(unittest.TestCase):
+
+    def test_thresholds(self):
+        policy = {"thresholds": [
+            {"metric": "judge.criteria.faithfulness", "min": 4.0, "max": 5},
+            {"metric": "judge.hallucination_rate", "max": 0.05},
+            {"metric": "metrics.context_recall", "min": 0.5},
+            {"metric": "metrics.answer_relevancy", "min": 0.5},
+        ]}
+        checks = check_policy(policy, SUMMARY)
+        self.assertEqual([(c.name, c.passed) for c in checks], [
+            ("judge.criteria.faithfulness >= 4", True),
+            ("judge.criteria.faithfulness <= 5", True),
+            ("judge.hallucination_rate <= 0.05", False),
+            ("metrics.context_recall >= 0.5", False),
+            ("metrics.answer_relevancy >= 0.5", False),
+        ])
+        self.assertIn("missing", checks[3].message)
+        self.assertIn("missing", checks[4].message)
+
+    def test_regression_limits(self):
+        policy = {"baseline": {"max_regressions": 2, "max_significant_regressions": 0}}
+        checks = check_policy(policy, SUMMARY, comparison(regressions=3, significant={"composite": -0.5, "mrr": 0.2}))
+        self.assertEqual([(c.passed, c.value) for c in checks], [(False, 3), (False, 1)])
+        self.assertIn("composite", checks[1].message)
+        self.assertNotIn("mrr", checks[1].message)
+
+        checks = check_policy(policy, SUMMARY, comparison(regressions=2))
+        self.assertTrue(all(c.passed for c in checks))
+        # without a comparison the baseline limits are not checked
+        self.assertEqual(check_policy(policy, SUMMARY), [])
+
+
+class ReportTest(unittest.TestCase):
+
+    def setUp(self):
+        self.report = GateReport(
+            suite="smoke",
+            run_id="run-1",
+            checks=[GateCheck("n_errors <= 0", True, 0, "n_errors = 0"),
+                    GateCheck("judge.hallucination_rate <= 0.05", False, 0.1, "judge.hallucination_rate = 0.1")],
+            baseline_run_id="base",
+        )
+
+    def test_junit_xml(self):
+        suite = ET.fromstring(to_junit_xml(self.report).split("\n", 1)[1])
+        self.assertEqual((suite.get("tests"), suite.get("failures")), ("2", "1"))
+        cases = suite.findall("testcase")
+        self.assertIsNone(cases[0].find("failure"))
+        self.assertEqual(cases[1].find("failure").get("message"), "judge.hallucination_rate = 0.1")
+
+    def test_markdown(self):
+        markdown = to_markdown(self.report, comparison())
+        self.assertIn("`smoke`: ❌ failed", markdown)
+        self.assertIn("vs baseline `base`", markdown)
+        self.assertIn("| `judge.hallucination_rate <= 0.05` | 0.1 | FAIL |", markdown)
+        self.assertIn("| `composite` | 4 | 3.5 | -0.5 | - |", markdown)
+
+
+if __name__ == "__main__":
+    unittest.main()
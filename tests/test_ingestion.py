@@ -0,0 +1,191 @@
+# tests/test_ingestion.py
+
+import os
+import tempfile
+import unittest
+from pathlib import Path
+
+from langchain_community.vectorstores import FAISS
+from langchain_core.documents import Document
+
+from evalrag.core.config import IngestionConfig
+from evalrag.core.ingestion import INDEX_STATE_FILE, Ingestion, normalize_source
+
+from .fakes import FakeEmbeddings
+
+
+class AssignChunkIdsTest(unittest.TestCase):
+
+    def test_relative_and_absolute_sources_share_a_doc_id(self):
+        absolute = str(Path("handbook.txt").resolve())
+        chunks = Ingestion.assign_chunk_ids([
+            Document(page_content="a", metadata={"source": "handbook.txt"}),
+            Document(page_content="b", metadata={"source": absolute}),
+            Document(page_content="c", metadata={"source": "./handbook.txt"}),
+        ])
+        self.assertEqual({c.metadata["doc_id"] for c in chunks}, {absolute})
+        self.assertEqual([c.metadata["chunk_id"] for c in chunks], [f"{absolute}#{n}" for n in range(3)])
+
+    def test_existing_ids_are_kept(self):
+        chunks = Ingestion.assign_chunk_ids([
+            Document(page_content="a", metadata={"doc_id": "doc", "chunk_id": "doc#intro"}),
+            Document(page_content="b", metadata={"doc_id": "doc"}),
+            Document(page_content="c", metadata={}),
+        ])
+        self.assertEqual([c.metadata["chunk_id"] for c in chunks], ["doc#intro", "doc#1", "unknown#0"])
+
+
+class IndexingTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.root = Path(self.tmp.name).resolve()
+        self.store_path = str(self.root / "index")
+        self.ingestion = Ingestion(IngestionConfig(
+            vector_store_path=self.store_path, chunker="sentence_window", chunker_params={}))
+        self.embeddings = FakeEmbeddings(dimension=16)
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def write(self, name, text):
+        path = self.root / name
+        path.write_text(text, encoding="utf-8")
+        return str(path)
+
+    def index(self, filename, embeddings=None, force=False):
+        return self.ingestion.indexing(filename=filename, embeddings=embeddings or self.embeddings, force=force)
+
+    def stored_ids(self):
+        store = FAISS.load_local(self.store_path, self.embeddings, allow_dangerous_deserialization=True)
+        return sorted(store.index_to_docstore_id.values())
+
+    def test_relative_and_absolute_paths_index_one_document(self):
+        path = self.write("handbook.txt", "The office opens at 8 am. Remote work is allowed.")
+
+        self.assertEqual(self.index(os.path.relpath(path)), 2)
+        self.assertEqual(self.index(path), 0)
+
+        state = self.ingestion.load_index_state(self.store_path)
+        self.assertEqual(list(state), [path])
+        self.assertEqual(state[path]["source"], path)
+        self.assertEqual(state[path]["chunk_ids"], [f"{path}#0", f"{path}#1"])
+        self.assertEqual(self.stored_ids(), state[path]["chunk_ids"])
+
+    def test_changed_file_replaces_its_chunks(self):
+        path = self.write("handbook.txt", "One. Two. Three.")
+        self.index(path)
+        self.write("handbook.txt", "Four. Five.")
+
+        self.assertEqual(self.index(os.path.relpath(path)), 2)
+
+        self.assertEqual(self.ingestion.load_index_state(self.store_path)[path]["chunk_ids"], [f"{path}#0", f"{path}#1"])
+        self.assertEqual(self.stored_ids(), [f"{path}#0", f"{path}#1"])
+
+    def test_existing_store_is_opened_without_embedding(self):
+        self.index(self.write("a.txt", "One. Two."))
+        # the new store embedded a probe text to size its index
+        self.assertEqual(self.embeddings.n_embedded, 3)
+        self.index(self.write("b.txt", "Three."))
+        self.assertEqual(self.embeddings.n_embedded, 4)
+
+    def test_embeddings_of_another_dimension_are_rejected(self):
+        self.index(self.write("a.txt", "One. Two."))
+        with self.assertRaises(ValueError):
+            self.index(self.write("b.txt", "Three."), embeddings=FakeEmbeddings(dimension=8))
+        self.assertEqual(list(self.ingestion.load_index_state(self.store_path)), [str(self.root / "a.txt")])
+
+    def test_delete_documents(self):
+        a, b = self.write("a.txt", "One. Two."), self.write("b.txt", "Three.")
+        self.index(a)
+        self.index(b)
+
+        self.assertEqual(self.ingestion.delete_documents(self.embeddings, [b], self.store_path), 1)
+
+        self.assertEqual(list(self.ingestion.load_index_state(self.store_path)), [a])
+        self.assertEqual(self.stored_ids(), [f"{a}#0", f"{a}#1"])
+
+    def test_chunks_of_a_store_without_state_are_found_by_source(self):
+        path = self.write("handbook.txt", "One. Two.")
+        relative = os.path.relpath(path)
+        self.ingestion.vector_store(self.embeddings, [
+            Document(page_content="Old.", metadata={"source": relative, "doc_id": relative, "chunk_id": "old#0"}),
+        ], self.store_path)
+        (Path(self.store_path) / INDEX_STATE_FILE).unlink()
+
+        self.index(path, force=True)
+
+        self.assertEqual(self.stored_ids(), [f"{path}#0", f"{path}#1"])
+        self.assertEqual(normalize_source(relative), path)
+
+
+class SyncTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.root = Path(self.tmp.name).resolve()
+        self.corpus = self.root / "corpus"
+        (self.corpus / "policies").mkdir(parents=True)
+        self.ingestion = Ingestion(IngestionConfig(
+            vector_store_path=str(self.root / "index"), chunker="sentence_window", chunker_params={}))
+        self.embeddings = FakeEmbeddings(dimension=16)
+        self.a = self.write("a.txt", "The office opens at 8 am.")
+        self.b = self.write("policies/b.txt", "Laptops are replaced every three years.")
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def write(self, name, text):
+        path = self.corpus / name
+        path.write_text(text, encoding="utf-8")
+        return str(path)
+
+    def sync(self, dir, dry_run=False):
+        return self.ingestion.sync_dir(dir, dry_run=dry_run, embeddings=self.embeddings)
+
+    def test_manifest_matches_however_the_dir_is_spelled(self):
+        self.assertEqual(self.sync(os.path.relpath(self.corpus)).added, [self.a, self.b])
+
+        for dir in (str(self.corpus), os.path.relpath(self.corpus) + "/.", str(self.corpus / "policies" / "..")):
+            plan = self.ingestion.plan_sync(dir)
+            self.assertEqual((plan.added, plan.updated, plan.deleted), ([], [], []), dir)
+            self.assertEqual(plan.unchanged, [self.a, self.b], dir)
+
+    def test_added_updated_and_deleted_files(self):
+        self.sync(str(self.corpus))
+        self.write("a.txt", "The office opens at 9 am. It closes at 6 pm.")
+        os.remove(self.b)
+        c = self.write("c.txt", "Expense reports are due monthly.")
+
+        plan = self.sync(os.path.relpath(self.corpus))
+
+        self.assertEqual((plan.added, plan.updated, plan.deleted, plan.unchanged), ([c], [self.a], [self.b], []))
+        state = self.ingestion.load_index_state(str(self.root / "index"))
+        self.assertEqual(sorted(state), [self.a, c])
+        self.assertEqual(len(state[self.a]["chunk_ids"]), 2)
+        self.assertEqual(self.sync(str(self.corpus), dry_run=True).unchanged, [self.a, c])
+
+    def test_touched_identical_file_is_not_reindexed(self):
+        self.sync(str(self.corpus))
+        os.utime(self.a, (1, 1))
+        n_embedded = self.embeddings.n_embedded
+
+        plan = self.sync(str(self.corpus))
+
+        self.assertEqual(plan.unchanged, [self.a, self.b])
+        self.assertEqual(self.embeddings.n_embedded, n_embedded)
+        self.assertEqual(self.ingestion.load_index_state(str(self.root / "index"))[self.a]["mtime"], 1)
+
+    def test_deletions_do_not_depend_on_the_working_directory(self):
+        self.addCleanup(os.chdir, os.getcwd())
+        os.chdir(self.corpus)
+        self.sync(".")
+        os.chdir(self.root)
+
+        plan = self.ingestion.plan_sync("corpus/policies")
+
+        self.assertEqual((plan.deleted, plan.unchanged), ([], [self.b]))
+
+
+if __name__ == "__main__":
+    unittest.main()
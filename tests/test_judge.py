@@ -0,0 +1,137 @@
+# tests/test_judge.py
+
+import json
+import threading
+import unittest
+
+from evalrag.core.eval.judge import JUDGE_OK, JUDGE_PARSE_ERROR, LLMJudge, parse_judge_output
+from evalrag.core.llm import FakeLLMClient
+
+TEMPLATE = "Q: {question}\nA: {answer}\nC: {context_block}"
+CONTEXTS = [{"text": "Paris is the capital of France."}]
+
+
+def judge_json(correctness=5, faithfulness=4, relevance=3) -> str:
+    return json.dumps({
+        "correctness": {"score": correctness, "rationale": "matches the context"},
+        "faithfulness": {"score": faithfulness, "rationale": "grounded"},
+        "relevance": {"score": relevance, "rationale": "on topic"},
+        "feedback": "good answer",
+    })
+
+
+class ParseJudgeOutputTest(unittest.TestCase):
+    def test_json_with_rationales(self):
+        result = parse_judge_output(judge_json())
+        self.assertEqual(result.status, JUDGE_OK)
+        self.assertEqual(result.scores, {"correctness": 5, "faithfulness": 4, "relevance": 3})
+        self.assertEqual(result.rationales["faithfulness"], "grounded")
+        self.assertEqual(result.feedback, "good answer")
+
+    def test_flat_json_in_code_fence(self):
+        text = 'Here you go:\n```json\n{"Correctness": 4, "Faithfulness": "5", "Relevance": 2}\n```'
+        result = parse_judge_output(text)
+        self.assertEqual(result.status, JUDGE_OK)
+        self.assertEqual(result.scores, {"correctness": 4, "faithfulness": 5, "relevance": 2})
+
+    def test_free_text_falls_back_to_regex(self):
+        text = "Correctness: 4 - mostly right\n**Faithfulness**: 5/5\nRelevance (score 3):\npartly off topic"
+        result = parse_judge_output(text)
+        self.assertEqual(result.status, JUDGE_OK)
+        self.assertEqual(result.scores, {"correctness": 4, "faithfulness": 5, "relevance": 3})
+        self.assertEqual(result.rationales["correctness"], "mostly right")
+        self.assertEqual(result.rationales["relevance"], "partly off topic")
+
+    def test_score_after_the_colon_wins_over_the_scale(self):
+        text = "Correctness (1-5): 4 - fine\nFaithfulness (1-5): 5\n\nRelevance [scale 1-5]: 2, partly off topic"
+        result = parse_judge_output(text)
+        self.assertEqual(result.status, JUDGE_OK)
+        self.assertEqual(result.scores, {"correctness": 4, "faithfulness": 5, "relevance": 2})
+        self.assertEqual(result.rationales["relevance"], "partly off topic")
+
+    def test_fractional_scores_are_parse_errors(self):
+        result = parse_judge_output('{"correctness": 4.7, "faithfulness": 5.0, "relevance": "3"}')
+        self.assertEqual(result.status, JUDGE_PARSE_ERROR)
+        self.assertEqual(result.scores, {"faithfulness": 5, "relevance": 3})
+        self.assertEqual(parse_judge_output("Correctness: 4.5\nFaithfulness: 5\nRelevance: 3").scores,
+                         {"faithfulness": 5, "relevance": 3})
+
+    def test_malformed_output_is_a_parse_error_without_defaults(self):
+        for text in ("", "I cannot rate this answer.", '{"correctness": 9, "faithfulness": "high"}'):
+            result = parse_judge_output(text)
+            self.assertEqual(result.status, JUDGE_PARSE_ERROR, text)
+            self.assertEqual(result.scores, {}, text)
+
+    def test_partial_output_keeps_parsed_criteria(self):
+        result = parse_judge_output('{"correctness": 4, "relevance": 2}')
+        self.assertEqual(result.status, JUDGE_PARSE_ERROR)
+        self.assertEqual(result.scores, {"correctness": 4, "relevance": 2})
+
+
+class LLMJudgeTest(unittest.TestCase):
+    def test_json_mode_appends_instructions(self):
+        client = FakeLLMClient([judge_json()])
+        judge = LLMJudge(client, TEMPLATE, json_instructions="Respond with JSON.")
+        result = judge.judge("Capital of France?", "Paris", CONTEXTS)
+        self.assertEqual(result.status, JUDGE_OK)
+        self.assertIn("Paris is the capital of France.", client.prompts[0])
+        self.assertTrue(client.prompts[0].endswith("Respond with JSON."))
+
+    def test_text_mode_parses_free_form(self):
+        client = FakeLLMClient(["Correctness: 2\nFaithfulness: 1\nRelevance: 4"])
+        judge = LLMJudge(client, TEMPLATE, json_instructions="Respond with JSON.", output_format="text")
+        result = judge.judge("Capital of France?", "Lyon", CONTEXTS)
+        self.assertEqual(result.scores, {"correctness": 2, "faithfulness": 1, "relevance": 4})
+        self.assertNotIn("Respond with JSON.", client.prompts[0])
+
+    def test_malformed_response(self):
+        judge = LLMJudge(FakeLLMClient(["no scores here"]), TEMPLATE)
+        result = judge.judge("Capital of France?", "Paris", CONTEXTS)
+        self.assertEqual(result.status, JUDGE_PARSE_ERROR)
+        self.assertEqual(result.raw, "no scores here")
+
+    def test_cache_reuses_identical_prompts(self):
+        client = FakeLLMClient([judge_json(5), judge_json(1)])
+        judge = LLMJudge(client, TEMPLATE)
+        first = judge.judge("Capital of France?", "Paris", CONTEXTS)
+        second = judge.judge("Capital of France?", "Paris", CONTEXTS)
+        self.assertIs(first, second)
+        self.assertEqual(client.calls, 1)
+
+        fresh = judge.judge("Capital of France?", "Paris", CONTEXTS, use_cache=False)
+        self.assertEqual(fresh.scores["correctness"], 1)
+        self.assertEqual(client.calls, 2)
+
+    def test_cache_evicts_least_recently_used(self):
+        client = FakeLLMClient([judge_json()])
+        judge = LLMJudge(client, TEMPLATE, cache_size=2)
+        for answer in ("a", "b", "a", "c"):
+            judge.judge("q", answer, CONTEXTS)
+        self.assertEqual(client.calls, 3)
+        judge.judge("q", "a", CONTEXTS)
+        self.assertEqual(client.calls, 3)
+        judge.judge("q", "b", CONTEXTS)
+        self.assertEqual(client.calls, 4)
+
+    def test_cache_is_thread_safe(self):
+        judge = LLMJudge(FakeLLMClient([judge_json()]), TEMPLATE, cache_size=4)
+        errors = []
+
+        def work(offset):
+            try:
+                for i in range(200):
+                    judge.judge("q", str((i + offset) % 16), CONTEXTS)
+            except Exception as e:  # pragma: no cover - reported below
+                errors.append(e)
+
+        threads = [threading.Thread(target=work, args=(n,)) for n in range(8)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        self.assertEqual(errors, [])
+        self.assertLessEqual(len(judge._cache), 4)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,119 @@
+# tests/test_loaders.py
+
+import json
+import tempfile
+import unittest
+from pathlib import Path
+
+from langchain_core.documents import Document
+
+from evalrag.core.ingestion import Ingestion
+from evalrag.core.loaders import LOADER_REGISTRY, get_loader, load_file, markdown_sections, register_loader
+
+MARKDOWN = """# Handbook
+Welcome aboard.
+
+## Hours
+The office opens at 8 am.
+
+```
+# not a heading
+```
+
+## Remote work
+Two days per week.
+"""
+
+
+class LoadersTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.root = Path(self.tmp.name)
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def write(self, name, text):
+        path = self.root / name
+        path.write_text(text, encoding="utf-8")
+        return str(path)
+
+    def test_jsonl_text_field_and_metadata(self):
+        path = self.write("faq.jsonl", "\n".join([
+            json.dumps({"content": "Refunds take five days.", "topic": "billing", "score": 3, "tags": ["a"]}),
+            "",
+            json.dumps({"question": "Who approves travel?", "answer": "Your manager.", "priority": 1}),
+            json.dumps("plain string record"),
+        ]))
+
+        docs = load_file(path)
+
+        self.assertEqual([d.page_content for d in docs], [
+            "Refunds take five days.",
+            "question: Who approves travel?\nanswer: Your manager.",
+            "plain string record",
+        ])
+        self.assertEqual(docs[0].metadata, {"topic": "billing", "score": 3, "source": path, "line": 1,
+                                            "file_type": "jsonl"})
+        self.assertEqual((docs[1].metadata["priority"], docs[1].metadata["line"]), (1, 3))
+        self.assertNotIn("question", docs[1].metadata)
+
+    def test_jsonl_reserved_fields_are_namespaced(self):
+        path = self.write("records.jsonl", "\n".join(
+            json.dumps({"text": f"Record {n}.", "doc_id": "kb", "chunk_id": "kb#0", "source": "crm", "line": 9})
+            for n in range(3)
+        ))
+
+        docs = load_file(path)
+
+        self.assertEqual(docs[0].metadata, {
+            "record_doc_id": "kb", "record_chunk_id": "kb#0", "record_source": "crm", "record_line": 9,
+            "source": path, "line": 1, "file_type": "jsonl",
+        })
+        chunk_ids = [d.metadata["chunk_id"] for d in Ingestion.assign_chunk_ids(docs)]
+        self.assertEqual(len(set(chunk_ids)), 3)
+        self.assertEqual(chunk_ids[0], f"{Path(path).resolve()}#0")
+
+    def test_csv_rows(self):
+        path = self.write("faq.csv", "question,answer\nWho approves travel?,Your manager.\n,\nWhen?,Monday.\n")
+        docs = load_file(path)
+        self.assertEqual([d.page_content for d in docs], [
+            "question: Who approves travel?\nanswer: Your manager.", "question: When?\nanswer: Monday.",
+        ])
+        self.assertEqual([d.metadata["row"] for d in docs], [1, 3])
+
+    def test_text_file(self):
+        docs = load_file(self.write("notes.txt", "Plain notes."))
+        self.assertEqual([(d.page_content, d.metadata["file_type"]) for d in docs], [("Plain notes.", "txt")])
+        self.assertEqual(load_file(self.write("empty.txt", "  \n")), [])
+
+    def test_markdown_sections(self):
+        docs = markdown_sections(MARKDOWN, "handbook.md")
+        self.assertEqual([d.metadata["heading_path"] for d in docs],
+                         ["Handbook", "Handbook > Hours", "Handbook > Remote work"])
+        self.assertTrue(docs[1].page_content.startswith("## Hours"))
+        self.assertIn("# not a heading", docs[1].page_content)
+        self.assertEqual([d.metadata["section"] for d in docs], [0, 1, 2])
+        self.assertEqual({d.metadata["source"] for d in docs}, {"handbook.md"})
+
+    def test_get_loader(self):
+        self.assertIs(get_loader("notes.TXT"), LOADER_REGISTRY[".txt"])
+        self.assertIs(get_loader("notes.bin", mime_type="text/markdown"), LOADER_REGISTRY[".md"])
+        with self.assertRaises(KeyError):
+            get_loader("archive.xyz")
+
+    def test_register_loader(self):
+        self.addCleanup(LOADER_REGISTRY.pop, ".rst", None)
+
+        @register_loader("rst")
+        def load_rst(path):
+            return [Document(page_content=Path(path).read_text(encoding="utf-8"))]
+
+        docs = load_file(self.write("guide.rst", "Title\n====="))
+        self.assertEqual(docs[0].metadata["file_type"], "rst")
+        self.assertIs(get_loader("guide.rst"), load_rst)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,100 @@
+# tests/test_metrics.py
+
+import json
+import unittest
+from dataclasses import replace
+
+from evalrag.core.config import EvalConfig
+from evalrag.core.eval import Evaluator
+from evalrag.core.eval.metrics import (
+    METRIC_OK,
+    METRIC_PARSE_ERROR,
+    METRIC_REGISTRY,
+    METRIC_SKIPPED,
+    Metric,
+    MetricResult,
+    Sample,
+    build_metrics,
+    get_metric,
+    register_metric,
+)
+from evalrag.core.llm import FakeLLMClient
+
+JUDGE_OUTPUT = json.dumps({"correctness": 5, "faithfulness": 2, "relevance": 4})
+CONTEXTS = [{"doc_id": "d", "chunk_id": "d#0", "text": "Paris is the capital of France."}]
+
+
+def evaluator(responses, metrics=("correctness", "faithfulness", "relevance")) -> Evaluator:
+    config = replace(
+        EvalConfig(),
+        judge_output_format="json",
+        metric_params={},
+        correctness_weight=0.5,
+        faithfulness_weight=0.3,
+        context_relevance_weight=0.2,
+        faithfulness_threshold=3.5,
+    )
+    return Evaluator(config, judge_client=FakeLLMClient(responses), metrics=list(metrics))
+
+
+class RegistryTest(unittest.TestCase):
+    def test_builtin_metrics_are_registered(self):
+        for name in ("correctness", "faithfulness", "relevance", "claim_faithfulness",
+                     "context_precision", "context_recall", "ir", "answer_relevancy"):
+            self.assertIn(name, METRIC_REGISTRY)
+
+    def test_custom_metric_with_params(self):
+        @register_metric
+        class AnswerLengthMetric(Metric):
+            name = "test_answer_length"
+            required_inputs = ("answer",)
+
+            def compute(self, sample):
+                return MetricResult(self.name, min(1.0, len(sample.answer) / self.params["max_chars"]))
+
+        try:
+            metric, = build_metrics(["test_answer_length"], params={"test_answer_length": {"max_chars": 10}})
+            self.assertEqual(metric.score(Sample(question="q", answer="Paris")).score, 0.5)
+            self.assertEqual(metric.score(Sample(question="q")).status, METRIC_SKIPPED)
+        finally:
+            del METRIC_REGISTRY["test_answer_length"]
+
+    def test_unknown_and_unnamed_metrics(self):
+        with self.assertRaises(KeyError):
+            get_metric("no_such_metric")
+        with self.assertRaises(ValueError):
+            register_metric(type("Unnamed", (Metric,), {}))
+
+
+class EvaluatorTest(unittest.TestCase):
+    def test_judge_metrics_share_one_judge_call(self):
+        ev = evaluator([JUDGE_OUTPUT])
+        results = ev.score_sample(Sample(question="Capital?", answer="Paris", contexts=CONTEXTS))
+        self.assertEqual(ev.judge_client.calls, 1)
+        self.assertEqual({k: r.score for k, r in results.items()},
+                         {"correctness": 1.0, "faithfulness": 0.25, "relevance": 0.75})
+        self.assertTrue(all(r.status == METRIC_OK for r in results.values()))
+
+    def test_missing_inputs_skip_metrics_without_calls(self):
+        ev = evaluator([JUDGE_OUTPUT])
+        results = ev.score_sample(Sample(question="Capital?", answer="Paris"))
+        self.assertEqual(results["faithfulness"].status, METRIC_SKIPPED)
+        self.assertEqual(results["faithfulness"].details["missing_inputs"], ["contexts"])
+        self.assertEqual(results["correctness"].status, METRIC_OK)
+
+    def test_unparsed_criterion_is_a_parse_error(self):
+        ev = evaluator(['{"correctness": 4}'])
+        results = ev.score_sample(Sample(question="Capital?", answer="Paris", contexts=CONTEXTS))
+        self.assertEqual(results["correctness"].score, 0.75)
+        self.assertEqual(results["faithfulness"].status, METRIC_PARSE_ERROR)
+        self.assertIsNone(results["faithfulness"].score)
+
+    def test_evaluate_answer_adds_composite_and_hallucination(self):
+        result = evaluator([JUDGE_OUTPUT]).evaluate_answer("Capital?", "Paris", CONTEXTS)
+        self.assertEqual(result["status"], "ok")
+        self.assertAlmostEqual(result["composite"], 0.5 * 1.0 + 0.3 * 0.25 + 0.2 * 0.75)
+        self.assertTrue(result["hallucination"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,236 @@
+# tests/test_qa_generation.py
+
+import random
+import tempfile
+import unittest
+from dataclasses import replace
+
+from langchain_core.documents import Document
+
+from evalrag.core.config import load_core_config, load_prompt_config
+from evalrag.core.eval.runner import resolve_dataset
+from evalrag.core.llm import FakeLLMClient
+from evalrag.datasets import DatasetRegistry, QAGenerator, build_generator, parse_qa_output, write_dataset
+from evalrag.datasets.qa_generation import UNANSWERABLE_ANSWER, allocate_question_types
+
+# chunk id to (document, text, question, answer)
+CORPUS = {
+    "handbook.md#0": ("handbook.md", "The office opens at 8 am on weekdays.",
+                      "When does the office open on weekdays?", "8 am"),
+    "handbook.md#1": ("handbook.md", "Remote work is allowed two days per week.",
+                      "How many remote days are allowed per week?", "Two days"),
+    "policies.md#0": ("policies.md", "Expense reports are due by the fifth of each month.",
+                      "When are expense reports due?", "By the fifth of each month"),
+    "policies.md#1": ("policies.md", "Laptops are replaced every three years.",
+                      "How often are laptops replaced?", "Every three years"),
+}
+
+
+def corpus_chunks():
+    return [
+        Document(page_content=text, metadata={"doc_id": doc_id, "chunk_id": chunk_id, "source": doc_id})
+        for chunk_id, (doc_id, text, _, _) in CORPUS.items()
+    ]
+
+
+def scripted_generation(prompt: str) -> str:
+    """
+    Answer a factoid generation prompt with the canned couple of the chunk
+    it contains.
+    """
+    for _, text, question, answer in CORPUS.values():
+        if text in prompt:
+            return f"Output:::\nFactoid question: {question}\nAnswer: {answer}"
+    return "Output:::\nI could not write a question."
+
+
+class ParseQAOutputTest(unittest.TestCase):
+
+    def test_question_and_answer(self):
+        text = "Output:::\nFactoid question: What is X?\nAnswer: Y\nOutput:::"
+        self.assertEqual(parse_qa_output(text), ("What is X?", "Y"))
+        self.assertEqual(parse_qa_output("Question: What is X?\nAnswer: a\nmultiline answer"),
+                         ("What is X?", "a\nmultiline answer"))
+
+    def test_question_only(self):
+        self.assertEqual(parse_qa_output("Question: What is Z?", with_answer=False), ("What is Z?", None))
+        self.assertIsNone(parse_qa_output("Question: What is Z?"))
+
+    def test_missing_parts(self):
+        for text in ("", "nothing here", "Question: \nAnswer: Y", "Question: X?\nAnswer: "):
+            self.assertIsNone(parse_qa_output(text), text)
+
+
+class BuildGeneratorTest(unittest.TestCase):
+
+    def setUp(self):
+        settings = load_core_config()
+        self.settings = replace(
+            settings,
+            generation=replace(settings.generation, seed=7, question_types={"factoid": 1.0}, max_answer_chars=30),
+            execution=replace(settings.execution, concurrency=3),
+        )
+
+    def test_generates_and_registers_a_testset_offline(self):
+        client = FakeLLMClient(scripted_generation, model_name="fake-writer")
+        generator = build_generator(self.settings, llm_client=client)
+        self.assertIs(generator.llm_client, client)
+        self.assertEqual(generator.executor.concurrency, 3)
+
+        pairs = generator.generate(corpus_chunks(), n_samples=3)
+
+        self.assertEqual(len(pairs), 3)
+        self.assertEqual(client.calls, 3)
+        self.assertEqual(generator.stats, {
+            "sampled": 3, "generated": 3, "dropped": 0, "errors": 0, "last_error": None,
+            "by_question_type": {"factoid": 3},
+        })
+        for pair in pairs:
+            doc_id, text, question, answer = CORPUS[pair.chunk_id]
+            self.assertEqual((pair.source_doc, pair.context, pair.question, pair.answer),
+                             (doc_id, text, question, answer))
+            self.assertEqual(pair.question_type, "factoid")
+            self.assertEqual(pair.metadata, {"generator_model": "fake-writer", "prompt": "qa_generation"})
+        self.assertEqual(len({p.id for p in pairs}), 3)
+
+        # the same seed samples the same chunks
+        again = build_generator(self.settings, llm_client=FakeLLMClient(scripted_generation))
+        self.assertEqual([p.id for p in again.generate(corpus_chunks(), n_samples=3)], [p.id for p in pairs])
+
+        with tempfile.TemporaryDirectory() as tmp:
+            version = write_dataset(pairs, output_dir=tmp, name="testset", metadata={"stats": generator.stats})
+            items, info = resolve_dataset(version.ref, registry=DatasetRegistry(tmp))
+
+        self.assertEqual(info["dataset"], version.ref)
+        self.assertEqual(version.manifest["n_items"], 3)
+        by_id = {item.id: item for item in items}
+        for pair in pairs:
+            item = by_id[pair.id]
+            self.assertEqual(item.gold_chunk_ids, [pair.chunk_id])
+            self.assertEqual(item.gold_doc_ids, [pair.source_doc])
+            self.assertEqual((item.reference, item.gold_context), (pair.answer, pair.context))
+            self.assertEqual(item.metadata["question_type"], "factoid")
+
+    def test_drops_unusable_generations_and_counts_errors(self):
+        def generation(prompt):
+            if "office" in prompt:
+                return "Sorry, no question."
+            if "Laptops" in prompt:
+                raise ConnectionError("provider down")
+            if "Expense" in prompt:
+                return "Question: When are expense reports due?\nAnswer: " + "very late " * 10
+            return scripted_generation(prompt)
+
+        generator = build_generator(self.settings, llm_client=FakeLLMClient(generation))
+        with self.assertLogs("evalrag.datasets.qa_generation", "WARNING") as logs:
+            pairs = generator.generate(corpus_chunks())
+
+        self.assertEqual([p.chunk_id for p in pairs], ["handbook.md#1"])
+        self.assertIn("policies.md#1", logs.output[0])
+        self.assertEqual(
+            (generator.stats["sampled"], generator.stats["generated"], generator.stats["dropped"],
+             generator.stats["errors"]),
+            (4, 1, 2, 1),
+        )
+        self.assertEqual(generator.stats["last_error"], "ConnectionError: provider down")
+
+    def test_programming_errors_propagate(self):
+        def generation(prompt):
+            raise KeyError("text")
+
+        generator = build_generator(self.settings, llm_client=FakeLLMClient(generation))
+        with self.assertRaises(KeyError):
+            generator.generate(corpus_chunks())
+
+
+# generation prompts naming their question type on the first line
+TYPED_PROMPTS = replace(
+    load_prompt_config(),
+    qa_generation_template="factoid\n{context}",
+    qa_multi_hop_template="multi_hop\n{context_a}\n{context_b}",
+    qa_comparison_template="comparison\n{context_a}\n{context_b}",
+    qa_numerical_template="numerical\n{context}",
+    qa_unanswerable_template="unanswerable\n{context}",
+    qa_noisy_template="noisy\n{context}",
+)
+
+
+def typed_generation(prompt: str) -> str:
+    """
+    Answer a `TYPED_PROMPTS` prompt, naming its question type in the couple.
+    """
+    kind = prompt.split("\n")[0]
+    return f"Output:::\nQuestion: {kind} question?\nAnswer: {kind} answer"
+
+
+class AllocateQuestionTypesTest(unittest.TestCase):
+
+    def test_counts_follow_the_mix(self):
+        types = allocate_question_types({"factoid": 0.5, "multi_hop": 0.3, "noisy": 0.2}, 10, random.Random(0))
+        self.assertEqual(sorted(types), sorted(["factoid"] * 5 + ["multi_hop"] * 3 + ["noisy"] * 2))
+
+    def test_largest_remainders(self):
+        types = allocate_question_types({"factoid": 1, "comparison": 1, "numerical": 1}, 4, random.Random(0))
+        self.assertEqual(len(types), 4)
+        self.assertEqual(sorted(set(types)), ["comparison", "factoid", "numerical"])
+
+    def test_seeded_shuffle(self):
+        mix = {"factoid": 1, "unanswerable": 1}
+        self.assertEqual(allocate_question_types(mix, 8, random.Random(3)),
+                         allocate_question_types(mix, 8, random.Random(3)))
+
+    def test_invalid_mix(self):
+        for mix in ({"trivia": 1.0}, {"factoid": -1.0, "noisy": 2.0}, {"factoid": 0}):
+            with self.assertRaises(ValueError):
+                allocate_question_types(mix, 4, random.Random(0))
+
+
+class QuestionTypesTest(unittest.TestCase):
+
+    def generate(self, question_types, chunks=None):
+        generator = QAGenerator(FakeLLMClient(typed_generation), TYPED_PROMPTS, seed=0, question_types=question_types)
+        return generator, generator.generate(chunks or corpus_chunks())
+
+    def test_multi_hop_pairs_chunks_of_one_document(self):
+        _, pairs = self.generate({"multi_hop": 1.0})
+        self.assertEqual(len(pairs), 4)
+        for pair in pairs:
+            self.assertEqual(pair.question_type, "multi_hop")
+            self.assertEqual(len(pair.chunk_id), 2)
+            self.assertEqual(len(set(pair.chunk_id)), 2)
+            self.assertEqual(len(set(pair.source_doc)), 1)
+            self.assertEqual(pair.context.split("\n\n---\n\n"), [CORPUS[c][1] for c in pair.chunk_id])
+            self.assertEqual(pair.metadata["prompt"], "qa_multi_hop")
+
+    def test_comparison_pairs_chunks_of_different_documents(self):
+        _, pairs = self.generate({"comparison": 1.0})
+        self.assertEqual(len(pairs), 4)
+        for pair in pairs:
+            self.assertEqual(pair.question, "comparison question?")
+            self.assertNotEqual(*pair.source_doc)
+
+    def test_unanswerable_questions_have_no_gold_chunks(self):
+        _, pairs = self.generate({"unanswerable": 1.0})
+        for pair in pairs:
+            self.assertEqual((pair.answer, pair.chunk_id, pair.source_doc), (UNANSWERABLE_ANSWER, [], []))
+            self.assertIn(pair.metadata["seed_chunk_id"], CORPUS)
+
+    def test_numerical_falls_back_to_factoid_without_digits(self):
+        generator, pairs = self.generate({"numerical": 1.0})
+        # only the office hours chunk holds a digit
+        self.assertEqual(generator.stats["by_question_type"], {"numerical": 1, "factoid": 3})
+        numerical = [p for p in pairs if p.question_type == "numerical"]
+        self.assertEqual([p.chunk_id for p in numerical], ["handbook.md#0"])
+
+    def test_two_chunk_types_fall_back_to_factoid_on_a_single_chunk(self):
+        _, pairs = self.generate({"multi_hop": 1.0}, chunks=corpus_chunks()[:1])
+        self.assertEqual([(p.question_type, p.chunk_id) for p in pairs], [("factoid", "handbook.md#0")])
+
+    def test_noisy_questions(self):
+        _, pairs = self.generate({"noisy": 1.0})
+        self.assertEqual({p.question for p in pairs}, {"noisy question?"})
+        self.assertEqual({p.metadata["prompt"] for p in pairs}, {"qa_noisy"})
+
+
+if __name__ == "__main__":
+    unittest.main()
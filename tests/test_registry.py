@@ -0,0 +1,152 @@
+# tests/test_registry.py
+
+import io
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stderr
+
+from evalrag import cli
+from evalrag.datasets import DatasetRegistry, assign_splits, content_hash
+from evalrag.datasets.registry import bump_version, parse_ref
+
+RECORDS = [{"id": f"q{i}", "question": f"question {i}", "chunk_id": f"doc#{i}"} for i in range(10)]
+
+
+def split_of(splits):
+    return {item_id: name for name, ids in splits.items() for item_id in ids}
+
+
+class VersionTest(unittest.TestCase):
+
+    def test_parse_ref(self):
+        self.assertEqual(parse_ref("testset@1.2.0"), ("testset", "1.2.0"))
+        self.assertEqual(parse_ref("testset"), ("testset", None))
+        self.assertEqual(parse_ref("testset@latest"), ("testset", None))
+
+    def test_bump_version(self):
+        self.assertEqual(bump_version(None), "1.0.0")
+        self.assertEqual(bump_version("1.2.3"), "1.3.0")
+        self.assertEqual(bump_version("1.2.3", "major"), "2.0.0")
+        self.assertEqual(bump_version("1.2.3", "patch"), "1.2.4")
+        with self.assertRaises(ValueError):
+            bump_version("1.2.3", "build")
+
+    def test_content_hash_ignores_key_order(self):
+        self.assertEqual(content_hash([{"id": "a", "x": 1}]), content_hash([{"x": 1, "id": "a"}]))
+        self.assertNotEqual(content_hash([{"id": "a", "x": 1}]), content_hash([{"id": "a", "x": 2}]))
+
+
+class DatasetRegistryTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.registry = DatasetRegistry(self.tmp.name)
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def test_register_resolve_and_load(self):
+        first = self.registry.register("testset", RECORDS[:5], provenance={"generator": "fake"})
+        second = self.registry.register("testset", RECORDS)
+
+        self.assertEqual((first.ref, second.ref), ("testset@1.0.0", "testset@1.1.0"))
+        self.assertEqual(self.registry.versions("testset"), ["1.0.0", "1.1.0"])
+        self.assertEqual(self.registry.resolve("testset").ref, "testset@1.1.0")
+        self.assertEqual(self.registry.load("testset@1.0.0"), RECORDS[:5])
+        self.assertEqual(first.manifest["provenance"], {"generator": "fake"})
+        self.assertEqual([v.ref for v in self.registry.list()], ["testset@1.1.0"])
+        self.assertTrue(self.registry.verify("testset@1.0.0"))
+
+    def test_identical_content_keeps_the_version(self):
+        first = self.registry.register("testset", RECORDS)
+        self.assertEqual(self.registry.register("testset", RECORDS).ref, first.ref)
+        self.assertEqual(self.registry.register("testset", RECORDS, bump="major").ref, first.ref)
+        self.assertEqual(self.registry.register("testset", RECORDS, version="3.0.0").ref, "testset@3.0.0")
+
+    def test_invalid_registrations(self):
+        self.registry.register("testset", RECORDS, version="1.0.0")
+        for kwargs in ({"name": "test@set"}, {"version": "1.0"}, {"version": "1.0.0"},
+                       {"splits": {"dev": ["missing"]}}):
+            with self.assertRaises(ValueError, msg=kwargs):
+                self.registry.register(**{"name": "testset", "items": RECORDS[:3], **kwargs})
+
+    def test_unknown_refs(self):
+        self.registry.register("testset", RECORDS)
+        for ref in ("other", "testset@9.9.9"):
+            with self.assertRaises(KeyError):
+                self.registry.resolve(ref)
+        with self.assertRaises(KeyError):
+            self.registry.load("testset", split="dev")
+
+    def test_splits(self):
+        splits = {"dev": ["q0", "q1"], "test": [f"q{i}" for i in range(2, 10)]}
+        version = self.registry.register("testset", RECORDS, splits=splits)
+        self.assertEqual([r["id"] for r in self.registry.load(version.ref, "dev")], ["q0", "q1"])
+        self.assertEqual({r["split"] for r in self.registry.load(version.ref, "test")}, {"test"})
+        self.assertEqual(version.manifest["splits"], splits)
+
+    def test_tampered_data_fails_verification(self):
+        version = self.registry.register("testset", RECORDS)
+        lines = version.path.read_text(encoding="utf-8").splitlines()
+        lines[0] = json.dumps({**RECORDS[0], "question": "edited"})
+        version.path.write_text("\n".join(lines) + "\n", encoding="utf-8")
+        self.assertFalse(self.registry.verify(version.ref))
+        with redirect_stderr(io.StringIO()):
+            code = cli.main(["dataset", "--registry", self.tmp.name, "verify", version.ref])
+        self.assertEqual(code, cli.EXIT_INTEGRITY)
+
+
+class AssignSplitsTest(unittest.TestCase):
+
+    def test_ratios_and_determinism(self):
+        records = [{"id": f"q{i}"} for i in range(100)]
+        splits = assign_splits(records, {"dev": 0.2, "test": 0.8}, seed=1)
+        self.assertEqual((len(splits["dev"]), len(splits["test"])), (20, 80))
+        self.assertEqual(splits, assign_splits(list(reversed(records)), {"dev": 0.2, "test": 0.8}, seed=1))
+        self.assertNotEqual(splits, assign_splits(records, {"dev": 0.2, "test": 0.8}, seed=2))
+
+    def test_items_keep_their_split_as_the_dataset_grows(self):
+        records = [{"id": f"q{i}"} for i in range(50)]
+        before = split_of(assign_splits(records[:40], {"dev": 1, "test": 1}))
+        after = split_of(assign_splits(records, {"dev": 1, "test": 1}))
+        moved = [item_id for item_id in before if before[item_id] != after[item_id]]
+        self.assertLessEqual(len(moved), 5)
+
+    def test_invalid_ratios(self):
+        with self.assertRaises(ValueError):
+            assign_splits(RECORDS, {"dev": 0, "test": 0})
+
+    def test_multi_hop_items_chain_their_chunks_into_one_group(self):
+        records = [
+            {"id": "a", "chunk_id": "doc#0"},
+            {"id": "b", "chunk_id": ["doc#0", "doc#1"]},
+            {"id": "c", "chunk_id": ["doc#1", "doc#2"]},
+            {"id": "d", "chunk_id": "doc#2"},
+        ] + [{"id": f"x{i}", "chunk_id": f"other#{i}"} for i in range(12)]
+        for seed in range(10):
+            splits = split_of(assign_splits(records, {"dev": 0.5, "test": 0.5}, seed=seed, group_key="chunk_id"))
+            self.assertEqual(len({splits[i] for i in "abcd"}), 1, seed)
+
+    def test_items_without_a_group_value_are_grouped_alone(self):
+        # unanswerable questions have no gold chunk
+        records = [{"id": f"u{i}", "chunk_id": []} for i in range(18)] + [{"id": "u18"}, {"id": "u19", "chunk_id": ""}]
+        splits = assign_splits(records, {"dev": 0.3, "test": 0.7}, group_key="chunk_id")
+        self.assertEqual((len(splits["dev"]), len(splits["test"])), (6, 14))
+
+    def test_no_chunk_is_shared_across_splits(self):
+        records = [
+            {"id": f"q{i}", "chunk_id": [f"doc#{i}", f"doc#{(i * 7) % 30}"] if i % 3 == 0 else f"doc#{i}"}
+            for i in range(30)
+        ]
+        splits = split_of(assign_splits(records, {"dev": 0.3, "test": 0.7}, seed=4, group_key="chunk_id"))
+        chunk_splits = {}
+        for record in records:
+            chunks = record["chunk_id"] if isinstance(record["chunk_id"], list) else [record["chunk_id"]]
+            for chunk in chunks:
+                chunk_splits.setdefault(chunk, set()).add(splits[record["id"]])
+        self.assertTrue(all(len(s) == 1 for s in chunk_splits.values()))
+
+
+if __name__ == "__main__":
+    unittest.main()
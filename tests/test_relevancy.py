@@ -0,0 +1,49 @@
+# tests/test_relevancy.py
+
+import json
+import unittest
+
+from evalrag.core.config import load_prompt_config
+from evalrag.core.eval.metrics import METRIC_PARSE_ERROR, Sample
+from evalrag.core.eval.relevancy import AnswerRelevancyMetric
+from evalrag.core.llm import FakeLLMClient
+
+from .fakes import FakeEmbeddings
+
+SAMPLE = Sample(question="How tall is the Eiffel Tower?", answer="The Eiffel Tower is 330 metres tall.")
+
+
+def metric(response, **params) -> AnswerRelevancyMetric:
+    return AnswerRelevancyMetric(
+        llm=FakeLLMClient([response]),
+        embedder=FakeEmbeddings(),
+        prompt_config=load_prompt_config(),
+        **params,
+    )
+
+
+class AnswerRelevancyTest(unittest.TestCase):
+    def test_identical_questions_score_one(self):
+        response = json.dumps({"questions": [SAMPLE.question] * 2, "noncommittal": False})
+        m = metric(response, n_questions=2)
+        result = m.score(SAMPLE)
+        self.assertAlmostEqual(result.score, 1.0)
+        self.assertEqual(len(result.details["generated_questions"]), 2)
+        self.assertIn("2", m.llm.prompts[0])
+
+    def test_off_topic_questions_score_lower(self):
+        on_topic = metric(json.dumps({"questions": ["How tall is the Eiffel Tower?"]})).score(SAMPLE).score
+        off_topic = metric(json.dumps({"questions": ["Which fruit has potassium?"]})).score(SAMPLE).score
+        self.assertLess(off_topic, on_topic)
+
+    def test_noncommittal_answers(self):
+        response = json.dumps({"questions": [SAMPLE.question], "noncommittal": "yes"})
+        self.assertEqual(metric(response).score(SAMPLE).score, 0.0)
+        self.assertAlmostEqual(metric(response, penalize_noncommittal=False).score(SAMPLE).score, 1.0)
+
+    def test_no_questions_is_a_parse_error(self):
+        self.assertEqual(metric("I can't think of any.").score(SAMPLE).status, METRIC_PARSE_ERROR)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,111 @@
+# tests/test_retrieval.py
+
+import json
+import unittest
+
+from evalrag.core.config import load_prompt_config
+from evalrag.core.eval.metrics import METRIC_OK, METRIC_PARSE_ERROR, METRIC_SKIPPED, Sample
+from evalrag.core.eval.retrieval import (
+    ContextPrecisionMetric,
+    ContextRecallMetric,
+    IRMetric,
+    average_precision,
+    gold_relevance,
+    ir_metrics,
+)
+from evalrag.core.llm import FakeLLMClient
+
+from .fakes import FakeEmbeddings
+
+CONTEXTS = [
+    {"doc_id": "a", "chunk_id": "a#0", "text": "The Eiffel Tower is in Paris."},
+    {"doc_id": "b", "chunk_id": "b#0", "text": "Bananas are rich in potassium."},
+    {"doc_id": "a", "chunk_id": "a#1", "text": "The Eiffel Tower is 330 metres tall."},
+]
+SAMPLE = Sample(
+    question="How tall is the Eiffel Tower in Paris?",
+    answer="330 metres.",
+    contexts=CONTEXTS,
+    reference="The Eiffel Tower is 330 metres tall. It was finished in 1889.",
+)
+
+
+def llm_metric(cls, responses, **params):
+    return cls(llm=FakeLLMClient(responses), prompt_config=load_prompt_config(), **params)
+
+
+class AveragePrecisionTest(unittest.TestCase):
+    def test_rank_aware(self):
+        self.assertEqual(average_precision([True, True, False]), 1.0)
+        self.assertAlmostEqual(average_precision([False, True, True]), (1 / 2 + 2 / 3) / 2)
+        self.assertEqual(average_precision([False, False]), 0.0)
+        self.assertEqual(average_precision([True, False], n_relevant=2), 0.5)
+
+
+class IRMetricsTest(unittest.TestCase):
+    def test_gold_chunk_ids_take_precedence(self):
+        self.assertEqual(gold_relevance(CONTEXTS, gold_chunk_ids=["a#1"], gold_doc_ids=["a"]), [False, False, True])
+
+    def test_gold_document_counts_once(self):
+        self.assertEqual(gold_relevance(CONTEXTS, gold_doc_ids=["a"]), [True, False, False])
+
+    def test_ir_metrics(self):
+        values = ir_metrics([False, True, False, True], n_relevant=2, k_values=[1, 2, 4])
+        self.assertEqual(values["hit_rate@1"], 0.0)
+        self.assertEqual(values["hit_rate@2"], 1.0)
+        self.assertEqual(values["recall@2"], 0.5)
+        self.assertEqual(values["recall@4"], 1.0)
+        self.assertEqual(values["mrr"], 0.5)
+        self.assertAlmostEqual(values["map"], (1 / 2 + 2 / 4) / 2)
+        self.assertAlmostEqual(values["ndcg@4"], (1 / 1.5849625 + 1 / 2.3219281) / (1 + 1 / 1.5849625), places=5)
+
+    def test_metric_needs_gold_ids(self):
+        metric = IRMetric(k_values=[1, 3])
+        self.assertEqual(metric.score(Sample(question="q", contexts=CONTEXTS)).status, METRIC_SKIPPED)
+        result = metric.score(Sample(question="q", contexts=CONTEXTS, gold_chunk_ids=["a#1"]))
+        self.assertAlmostEqual(result.score, 1 / 3)
+        self.assertEqual(result.details["hit_rate@1"], 0.0)
+        self.assertEqual(result.details["hit_rate@3"], 1.0)
+
+
+class ContextPrecisionTest(unittest.TestCase):
+    def test_llm_verdicts(self):
+        verdicts = json.dumps({"verdicts": [
+            {"context": 1, "relevant": True}, {"context": 2, "relevant": False}, {"context": 3, "relevant": True},
+        ]})
+        result = llm_metric(ContextPrecisionMetric, [verdicts]).score(SAMPLE)
+        self.assertEqual(result.status, METRIC_OK)
+        self.assertAlmostEqual(result.score, (1 + 2 / 3) / 2)
+        self.assertEqual([c["relevant"] for c in result.details["contexts"]], [True, False, True])
+
+    def test_missing_verdict_is_a_parse_error(self):
+        result = llm_metric(ContextPrecisionMetric, ["1. yes\n2. no"]).score(SAMPLE)
+        self.assertEqual(result.status, METRIC_PARSE_ERROR)
+
+    def test_embedding_mode(self):
+        metric = ContextPrecisionMetric(mode="embedding", similarity_threshold=0.6, embedder=FakeEmbeddings())
+        self.assertTrue(metric.needs_embedder)
+        result = metric.score(SAMPLE)
+        self.assertEqual([c["relevant"] for c in result.details["contexts"]], [True, False, True])
+
+    def test_unknown_mode(self):
+        with self.assertRaises(ValueError):
+            ContextPrecisionMetric(mode="magic")
+
+
+class ContextRecallTest(unittest.TestCase):
+    def test_llm_attribution(self):
+        verdicts = json.dumps({"verdicts": [{"statement": 1, "attributed": True}, {"statement": 2, "attributed": False}]})
+        metric = llm_metric(ContextRecallMetric, [verdicts])
+        result = metric.score(SAMPLE)
+        self.assertEqual(result.score, 0.5)
+        self.assertEqual(len(result.details["statements"]), 2)
+        self.assertIn("It was finished in 1889.", metric.llm.prompts[0])
+
+    def test_without_reference(self):
+        result = llm_metric(ContextRecallMetric, []).score(Sample(question="q", contexts=CONTEXTS))
+        self.assertEqual(result.status, METRIC_SKIPPED)
+
+
+if __name__ == "__main__":
+    unittest.main()
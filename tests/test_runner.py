@@ -0,0 +1,102 @@
+# tests/test_runner.py
+
+import json
+import tempfile
+import unittest
+from dataclasses import replace
+from pathlib import Path
+
+from evalrag.core.config import EvalConfig
+from evalrag.core.eval import Evaluator, EvaluationRunner
+from evalrag.core.eval.runner import RESULTS_FILE, SUMMARY_FILE, item_from_record, read_results
+from evalrag.core.llm import FakeLLMClient
+
+JUDGE_OUTPUT = json.dumps({"correctness": 5, "faithfulness": 5, "relevance": 3})
+DATASET = [
+    {"id": "q1", "question": "What is the capital of France?", "answer": "Paris", "chunk_id": "geo#0"},
+    {"id": "q2", "question": "What is the capital of Spain?", "answer": "Madrid", "chunk_id": "geo#1"},
+    {"id": "q3", "question": "What is the capital of Italy?", "answer": "Rome", "chunk_id": "geo#2"},
+]
+
+
+class FakeRAG:
+    """
+    Answers from a fixed table, retrieving `geo#0` first; questions in
+    `failing` raise.
+    """
+
+    def __init__(self, failing=()) -> None:
+        self.failing = set(failing)
+        self.questions = []
+        self.top_k = 2
+
+    def generate_answer(self, question, **kwargs):
+        self.questions.append(question)
+        if question in self.failing:
+            raise ConnectionError("provider unreachable")
+        contexts = [
+            {"doc_id": "geo", "chunk_id": "geo#0", "text": "Paris is the capital of France.", "score": 0.9},
+            {"doc_id": "geo", "chunk_id": "geo#1", "text": "Madrid is the capital of Spain.", "score": 0.8},
+        ]
+        return {"answer": question.split()[-1], "contexts": contexts, "meta": {"latency_ms": 10.0}}
+
+
+def evaluator() -> Evaluator:
+    config = replace(EvalConfig(), judge_output_format="json", metric_params={"ir": {"k_values": [1, 2]}})
+    return Evaluator(config, judge_client=FakeLLMClient([JUDGE_OUTPUT]), metrics=["ir"])
+
+
+class RunnerTest(unittest.TestCase):
+    def setUp(self):
+        self.dir = Path(tempfile.mkdtemp())
+        self.dataset = self.dir / "dataset.jsonl"
+        self.dataset.write_text("\n".join(json.dumps(r) for r in DATASET) + "\n", encoding="utf-8")
+
+    def test_item_from_record_columns(self):
+        item = item_from_record({"question": " Q? ", "ground_truth": "A", "gold_doc_id": "a|b", "topic": "geo"})
+        self.assertEqual(item.question, "Q?")
+        self.assertEqual(item.reference, "A")
+        self.assertEqual(item.gold_doc_ids, ["a", "b"])
+        self.assertEqual(item.metadata, {"topic": "geo"})
+        self.assertEqual(len(item.id), 16)
+
+    def test_run_writes_results_and_summary(self):
+        runner = EvaluationRunner(FakeRAG(), evaluator(), runs_dir=str(self.dir / "runs"), concurrency=2)
+        summary = runner.run(str(self.dataset), run_id="r1")
+
+        self.assertEqual((summary["n_items"], summary["n_ok"], summary["n_errors"]), (3, 3, 0))
+        self.assertEqual(summary["dataset"], str(self.dataset))
+        self.assertAlmostEqual(summary["metrics"]["ir.hit_rate@2"], 2 / 3)
+        self.assertAlmostEqual(summary["metrics"]["ir.mrr"], (1 + 1 / 2) / 3)
+        self.assertEqual(summary["judge"]["n_scored"], 3)
+        self.assertEqual(summary["latency_ms_mean"], 10.0)
+        saved = json.loads((self.dir / "runs" / "r1" / SUMMARY_FILE).read_text(encoding="utf-8"))
+        self.assertEqual(saved["n_ok"], 3)
+
+    def test_resume_retries_failed_items_only(self):
+        runs_dir = str(self.dir / "runs")
+        failing = FakeRAG(failing=["What is the capital of Spain?"])
+        summary = EvaluationRunner(failing, evaluator(), runs_dir=runs_dir).run(str(self.dataset), run_id="r2")
+        self.assertEqual(summary["n_errors"], 1)
+        self.assertIn("ConnectionError", read_results(Path(runs_dir) / "r2")["q2"]["error"])
+
+        rag = FakeRAG()
+        summary = EvaluationRunner(rag, evaluator(), runs_dir=runs_dir).run(str(self.dataset), run_id="r2")
+        self.assertEqual(rag.questions, ["What is the capital of Spain?"])
+        self.assertEqual((summary["n_ok"], summary["n_errors"]), (3, 0))
+
+    def test_truncated_results_line_is_ignored(self):
+        runs_dir = self.dir / "runs"
+        EvaluationRunner(FakeRAG(), evaluator(), runs_dir=str(runs_dir)).run(str(self.dataset), run_id="r3", limit=1)
+        with open(runs_dir / "r3" / RESULTS_FILE, "a", encoding="utf-8") as f:
+            f.write('{"id": "q2", "status": "o')
+
+        rag = FakeRAG()
+        summary = EvaluationRunner(rag, evaluator(), runs_dir=str(runs_dir)).run(str(self.dataset), run_id="r3")
+        self.assertEqual(len(rag.questions), 2)
+        self.assertEqual(summary["n_ok"], 3)
+        self.assertEqual(len(read_results(runs_dir / "r3")), 3)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,76 @@
+# tests/test_scoring.py
+
+import unittest
+import warnings
+from dataclasses import replace
+
+from evalrag.core.config import EvalConfig
+from evalrag.core.eval.scoring import aggregate_scores, composite_score, is_hallucination, resolve_weights
+
+WEIGHTS = {"correctness": 0.5, "faithfulness": 0.3, "relevance": 0.2}
+
+
+class ResolveWeightsTest(unittest.TestCase):
+    def test_weights_summing_to_one_are_kept(self):
+        config = replace(EvalConfig(), correctness_weight=0.5, faithfulness_weight=0.3, context_relevance_weight=0.2)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            self.assertEqual(resolve_weights(config), WEIGHTS)
+
+    def test_other_sums_are_renormalized_with_a_warning(self):
+        config = replace(EvalConfig(), correctness_weight=2, faithfulness_weight=1, context_relevance_weight=1)
+        with self.assertWarns(UserWarning):
+            weights = resolve_weights(config)
+        self.assertEqual(weights, {"correctness": 0.5, "faithfulness": 0.25, "relevance": 0.25})
+
+    def test_invalid_weights(self):
+        for values in ((-1, 1, 1), (0, 0, 0)):
+            config = replace(
+                EvalConfig(),
+                correctness_weight=values[0],
+                faithfulness_weight=values[1],
+                context_relevance_weight=values[2],
+            )
+            with self.assertRaises(ValueError):
+                resolve_weights(config)
+
+
+class CompositeScoreTest(unittest.TestCase):
+    def test_normalized_weighted_mean(self):
+        self.assertEqual(composite_score({"correctness": 5, "faithfulness": 5, "relevance": 5}, WEIGHTS), 1.0)
+        self.assertEqual(composite_score({"correctness": 1, "faithfulness": 1, "relevance": 1}, WEIGHTS), 0.0)
+        self.assertAlmostEqual(composite_score({"correctness": 3, "faithfulness": 5, "relevance": 1}, WEIGHTS), 0.55)
+
+    def test_missing_weighted_criterion(self):
+        self.assertIsNone(composite_score({"correctness": 5, "relevance": 5}, WEIGHTS))
+        self.assertEqual(composite_score({"correctness": 5}, {"correctness": 1.0, "relevance": 0.0}), 1.0)
+
+    def test_hallucination_flag(self):
+        self.assertTrue(is_hallucination({"faithfulness": 3}, 3.5))
+        self.assertFalse(is_hallucination({"faithfulness": 4}, 3.5))
+        self.assertIsNone(is_hallucination({"correctness": 4}, 3.5))
+
+
+class AggregateScoresTest(unittest.TestCase):
+    def test_parse_errors_are_excluded_from_means(self):
+        results = [
+            {"scores": {"correctness": 5, "faithfulness": 5, "relevance": 5}, "composite": 1.0, "hallucination": False},
+            {"scores": {"correctness": 1, "faithfulness": 2, "relevance": 3}, "composite": 0.2, "hallucination": True},
+            {"scores": {"correctness": 3}, "composite": None, "hallucination": None},
+        ]
+        summary = aggregate_scores(results)
+        self.assertEqual(summary["n"], 3)
+        self.assertEqual(summary["n_scored"], 2)
+        self.assertEqual(summary["parse_errors"], 1)
+        self.assertAlmostEqual(summary["composite_mean"], 0.6)
+        self.assertEqual(summary["hallucination_rate"], 0.5)
+        self.assertEqual(summary["criteria"], {"correctness": 3.0, "faithfulness": 3.5, "relevance": 4.0})
+
+    def test_empty_run(self):
+        summary = aggregate_scores([])
+        self.assertIsNone(summary["composite_mean"])
+        self.assertIsNone(summary["hallucination_rate"])
+
+
+if __name__ == "__main__":
+    unittest.main()
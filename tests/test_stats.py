@@ -0,0 +1,77 @@
+# tests/test_stats.py
+
+import math
+import unittest
+
+from evalrag.core.eval.stats import (
+    minimum_detectable_effect,
+    paired_bootstrap_ci,
+    paired_permutation_test,
+    significance_report,
+    wilcoxon_test,
+)
+
+BASE = [3.0, 4.0, 2.0, 5.0, 3.0, 4.0, 2.0, 3.0, 4.0, 3.0, 2.0, 4.0]
+
+
+class PairedTestsTest(unittest.TestCase):
+
+    def test_identical_runs_are_not_significant(self):
+        self.assertEqual(paired_permutation_test(BASE, BASE), 1.0)
+        self.assertEqual(wilcoxon_test(BASE, BASE), 1.0)
+        self.assertEqual(paired_bootstrap_ci(BASE, BASE), (0.0, 0.0))
+
+    def test_consistent_improvement_is_significant(self):
+        candidate = [b + 1.0 + 0.1 * (i % 3) for i, b in enumerate(BASE)]
+        self.assertLess(paired_permutation_test(BASE, candidate), 0.01)
+        self.assertLess(wilcoxon_test(BASE, candidate), 0.01)
+        low, high = paired_bootstrap_ci(BASE, candidate)
+        self.assertGreater(low, 0.9)
+        self.assertLess(high, 1.3)
+
+    def test_noise_is_not_significant(self):
+        candidate = [b + (0.5 if i % 2 else -0.5) for i, b in enumerate(BASE)]
+        self.assertGreater(paired_permutation_test(BASE, candidate), 0.5)
+        low, high = paired_bootstrap_ci(BASE, candidate)
+        self.assertLess(low, 0.0)
+        self.assertGreater(high, 0.0)
+
+    def test_seed_makes_resampling_reproducible(self):
+        candidate = [b + (1.0 if i % 3 else -0.5) for i, b in enumerate(BASE)]
+        self.assertEqual(paired_bootstrap_ci(BASE, candidate, seed=7), paired_bootstrap_ci(BASE, candidate, seed=7))
+        self.assertEqual(paired_permutation_test(BASE, candidate, seed=7), paired_permutation_test(BASE, candidate, seed=7))
+
+    def test_unpaired_lengths_raise(self):
+        with self.assertRaises(ValueError):
+            paired_permutation_test([1.0, 2.0], [1.0])
+
+    def test_minimum_detectable_effect(self):
+        self.assertEqual(minimum_detectable_effect([1.0], [2.0]), float("inf"))
+        candidate = [b + (0.5 if i % 2 else -0.5) for i, b in enumerate(BASE)]
+        small = minimum_detectable_effect(BASE, candidate)
+        # four times the items halves the detectable effect
+        large = minimum_detectable_effect(BASE * 4, candidate * 4)
+        self.assertAlmostEqual(large / small, 0.5, delta=0.05)
+
+
+class SignificanceReportTest(unittest.TestCase):
+
+    def test_report_fields(self):
+        candidate = [b + 1.0 for b in BASE]
+        report = significance_report(BASE, candidate, alpha=0.05, n_resamples=2000)
+        self.assertEqual(report["n"], len(BASE))
+        self.assertAlmostEqual(report["mean_delta"], 1.0)
+        self.assertAlmostEqual(report["ci_low"], 1.0)
+        self.assertAlmostEqual(report["ci_high"], 1.0)
+        self.assertTrue(report["significant"])
+        self.assertEqual(report["min_detectable_effect"], 0.0)
+
+    def test_empty_report(self):
+        report = significance_report([], [])
+        self.assertEqual(report["n"], 0)
+        self.assertTrue(math.isnan(report["mean_delta"]))
+        self.assertFalse(report["significant"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,187 @@
+# tests/test_store.py
+
+import json
+import tempfile
+import unittest
+from pathlib import Path
+
+from evalrag.core.eval.store import RunStore, record_scores
+
+
+def record(item_id, composite, relevance=None, status="ok"):
+    """
+    Runner result record with a judge composite and an optional metric.
+    """
+    metrics = {}
+    if relevance is not None:
+        metrics["answer_relevancy"] = {"score": relevance, "details": {"n_questions": 3, "model": "fake"}}
+    return {
+        "id": item_id,
+        "status": status,
+        "question": f"question {item_id}",
+        "reference": f"reference {item_id}",
+        "answer": f"answer {item_id}",
+        "contexts": [{"chunk_id": f"doc#{item_id}", "text": "context"}],
+        "judge": {"composite": composite, "scores": {"correctness": 4, "faithfulness": 5, "relevance": 3}},
+        "metrics": metrics,
+    }
+
+
+class RecordScoresTest(unittest.TestCase):
+
+    def test_flattens_judge_and_metric_scores(self):
+        scores = record_scores(record("q1", 4.2, relevance=0.8))
+        self.assertEqual(scores, {
+            "composite": 4.2,
+            "judge.correctness": 4,
+            "judge.faithfulness": 5,
+            "judge.relevance": 3,
+            "answer_relevancy": 0.8,
+            "answer_relevancy.n_questions": 3,
+        })
+
+    def test_empty_record(self):
+        self.assertEqual(record_scores({"id": "q1"}), {})
+
+
+class RunStoreTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.store = RunStore(f"sqlite:///{Path(self.tmp.name) / 'db' / 'runs.db'}")
+
+    def tearDown(self):
+        self.store.engine.dispose()
+        self.tmp.cleanup()
+
+    def test_run_lifecycle(self):
+        self.store.start_run("run-1", dataset="testset.jsonl", config={"top_k": 5}, judge_model="judge-m")
+        self.store.save_item("run-1", record("q1", 4.0))
+        self.store.finish_run("run-1", {"n": 1})
+
+        run = self.store.get_run("run-1")
+        self.assertEqual(run["name"], "run-1")
+        self.assertEqual(run["config"], {"top_k": 5})
+        self.assertEqual(run["judge_model"], "judge-m")
+        self.assertEqual(run["summary"], {"n": 1})
+        self.assertIsNotNone(run["finished_at"])
+        self.assertIsNone(self.store.get_run("missing"))
+        self.assertEqual([r["run_id"] for r in self.store.list_runs()], ["run-1"])
+
+    def test_resuming_keeps_original_metadata(self):
+        self.store.start_run("run-1", dataset="first.jsonl")
+        self.store.start_run("run-1", dataset="second.jsonl")
+        self.assertEqual(self.store.get_run("run-1")["dataset"], "first.jsonl")
+
+    def test_save_item_replaces_previous_record(self):
+        self.store.start_run("run-1")
+        self.store.save_item("run-1", record("q1", 2.0, relevance=0.5, status="error"))
+        self.store.save_item("run-1", record("q1", 4.0))
+
+        items = self.store.get_items("run-1")
+        self.assertEqual(len(items), 1)
+        self.assertEqual(items[0]["status"], "ok")
+        self.assertEqual(items[0]["contexts"][0]["chunk_id"], "doc#q1")
+        # stale metric scores of the replaced record are dropped
+        self.assertEqual(self.store.item_scores("run-1")["q1"]["composite"], 4.0)
+        self.assertNotIn("answer_relevancy", self.store.item_scores("run-1")["q1"])
+
+    def test_compare_reports_deltas_regressions_and_wins(self):
+        for run_id, composites in (("base", [4.0, 3.0, 2.0, 5.0]), ("cand", [3.0, 3.1, 4.0])):
+            self.store.start_run(run_id)
+            for i, composite in enumerate(composites):
+                self.store.save_item(run_id, record(f"q{i}", composite))
+
+        comparison = self.store.compare("base", "cand", tolerance=0.5, significance=False)
+
+        self.assertEqual(comparison.only_in_base, ["q3"])
+        self.assertEqual(comparison.only_in_candidate, [])
+        delta = comparison.metric_deltas["composite"]
+        self.assertEqual(delta["n_paired"], 3)
+        self.assertAlmostEqual(delta["base_mean"], 3.0)
+        self.assertAlmostEqual(delta["candidate_mean"], 10.1 / 3)
+        # q1 moved by 0.1, within the tolerance
+        self.assertEqual([(c["item_id"], c["delta"]) for c in comparison.regressions], [("q0", -1.0)])
+        self.assertEqual([(c["item_id"], c["delta"]) for c in comparison.wins], [("q2", 2.0)])
+        self.assertEqual(comparison.significance, {})
+
+    def test_compare_defaults_to_top_level_metrics(self):
+        for run_id in ("base", "cand"):
+            self.store.start_run(run_id)
+            self.store.save_item(run_id, record("q0", 4.0, relevance=0.5))
+
+        comparison = self.store.compare("base", "cand", significance=False)
+        self.assertEqual(sorted(comparison.metric_deltas), ["answer_relevancy", "composite"])
+        self.assertEqual(comparison.regressions + comparison.wins, [])
+
+    def test_compare_skips_metrics_missing_from_a_run(self):
+        self.store.start_run("base")
+        self.store.save_item("base", record("q0", 4.0, relevance=0.5))
+        self.store.start_run("cand")
+        self.store.save_item("cand", record("q0", 4.0))
+
+        comparison = self.store.compare("base", "cand", metrics=["answer_relevancy"], significance=False)
+        self.assertEqual(comparison.metric_deltas, {})
+
+
+class AnnotationTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.store = RunStore(f"sqlite:///{Path(self.tmp.name) / 'runs.db'}")
+        self.store.start_run("run-1", judge_model="judge-m")
+        for item_id in ("q0", "q1", "q2"):
+            self.store.save_item("run-1", record(item_id, 4.0))
+
+    def tearDown(self):
+        self.store.engine.dispose()
+        self.tmp.cleanup()
+
+    def test_add_annotation_validates_scores(self):
+        with self.assertRaises(ValueError):
+            self.store.add_annotation("run-1", "q0", "ann", {"fluency": 3})
+        with self.assertRaises(ValueError):
+            self.store.add_annotation("run-1", "q0", "ann", {"correctness": 6})
+
+    def test_reviewer_keeps_one_annotation_per_item(self):
+        self.store.add_annotation("run-1", "q0", "ann", {"correctness": 2}, comment="first")
+        self.store.add_annotation("run-1", "q0", "ann", {"correctness": 4, "relevance": None}, comment="second")
+        annotations = self.store.get_annotations("run-1", "q0")
+        self.assertEqual(len(annotations), 1)
+        self.assertEqual((annotations[0]["correctness"], annotations[0]["comment"]), (4, "second"))
+        # unrated criteria stay empty instead of defaulting to a score
+        self.assertIsNone(annotations[0]["relevance"])
+        self.assertIsNone(annotations[0]["faithfulness"])
+
+    def test_human_scores_average_reviewers_and_skip_unrated(self):
+        self.store.add_annotation("run-1", "q0", "ann", {"correctness": 2, "faithfulness": 5})
+        self.store.add_annotation("run-1", "q0", "bob", {"correctness": 3})
+        self.assertEqual(self.store.human_scores("run-1"), {"q0": {"correctness": 2.5, "faithfulness": 5.0}})
+
+    def test_calibration_ratings_round_half_up(self):
+        self.store.add_annotation("run-1", "q0", "ann", {"correctness": 2, "faithfulness": 4})
+        self.store.add_annotation("run-1", "q0", "bob", {"correctness": 3, "faithfulness": 5})
+        self.store.add_annotation("run-1", "q2", "ann", {"correctness": 1})
+
+        ratings, human = self.store.calibration_ratings("run-1")
+        self.assertEqual(ratings, {"judge-m": {
+            "correctness": [4, 4], "faithfulness": [5, 5], "relevance": [3, 3],
+        }})
+        self.assertEqual(human, {"correctness": [3, 1], "faithfulness": [5, None], "relevance": [None, None]})
+
+    def test_export_annotations(self):
+        self.store.add_annotation("run-1", "q1", "ann", {"correctness": 4, "relevance": 2}, comment="vague")
+        self.store.add_annotation("run-1", "q1", "bob", {"correctness": 5})
+        path = Path(self.tmp.name) / "export" / "calibration.jsonl"
+
+        self.assertEqual(self.store.export_annotations("run-1", str(path)), 1)
+        rows = [json.loads(line) for line in path.read_text(encoding="utf-8").splitlines()]
+        self.assertEqual(len(rows), 1)
+        self.assertEqual(rows[0]["id"], "q1")
+        self.assertEqual(rows[0]["question"], "question q1")
+        self.assertEqual(rows[0]["human"], {"correctness": 5, "relevance": 2})
+        self.assertEqual(rows[0]["comments"], ["vague"])
+
+
+if __name__ == "__main__":
+    unittest.main()